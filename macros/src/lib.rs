@@ -0,0 +1,224 @@
+#![warn(clippy::pedantic)]
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input, Expr, Ident, LitStr, Path, Token, Type,
+};
+
+/// Parses a line according to a pattern, e.g.
+/// `scan!(line, "Game {u64}: {rest}")` returns `Result<(u64, &str), ParseError>`.
+///
+/// Captures are `{type}` for anything implementing `aoc::parse::Scan`, `{}` to
+/// infer the type, `{word}` for a run of non-whitespace and `{rest}` for the
+/// remaining input. A single capture is returned as is, several as a tuple.
+/// Named captures like `{l:u64}x{w:u64}` can fill a struct instead:
+/// `scan!(line, "{l:u64}x{w:u64}" => Rect)`. Runs of spaces in the pattern
+/// match one or more spaces or tabs, `{{` and `}}` match literal braces.
+#[proc_macro]
+pub fn scan(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as Scan);
+
+    input
+        .expand()
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+struct Scan {
+    input: Expr,
+    pattern: LitStr,
+    target: Option<Path>,
+}
+
+impl Parse for Scan {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ret_input = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let pattern = input.parse()?;
+        let target = if input.parse::<Option<Token![=>]>>()?.is_some() {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        input.parse::<Option<Token![,]>>()?;
+
+        Ok(Self {
+            input: ret_input,
+            pattern,
+            target,
+        })
+    }
+}
+
+enum Segment {
+    Literal(String),
+    Spaces,
+    Capture { name: Option<Ident>, kind: Kind },
+}
+
+enum Kind {
+    Infer,
+    Rest,
+    Word,
+    Type(Box<Type>),
+}
+
+impl Scan {
+    fn expand(&self) -> syn::Result<TokenStream2> {
+        let segments = segments(&self.pattern)?;
+
+        let mut statements = Vec::new();
+        let mut captures = Vec::new();
+        for segment in segments {
+            match segment {
+                Segment::Literal(v) => statements.push(quote! {
+                    ::aoc::parse::__scan::literal(input, #v)?;
+                }),
+                Segment::Spaces => statements.push(quote! {
+                    ::aoc::parse::__scan::spaces(input)?;
+                }),
+                Segment::Capture { name, kind } => {
+                    let binding = format_ident!("__capture_{}", captures.len());
+                    let parser = match kind {
+                        Kind::Infer => quote!(::aoc::parse::Scan::scan(input)),
+                        Kind::Rest => quote!(::aoc::parse::__scan::rest(input)),
+                        Kind::Word => quote!(::aoc::parse::__scan::word(input)),
+                        Kind::Type(ty) => quote!(<#ty as ::aoc::parse::Scan>::scan(input)),
+                    };
+                    statements.push(quote! {
+                        let #binding = #parser?;
+                    });
+                    captures.push((name, binding));
+                }
+            }
+        }
+
+        let output = if let Some(target) = &self.target {
+            let fields = captures
+                .iter()
+                .map(|(name, binding)| {
+                    let name = name.as_ref().ok_or_else(|| {
+                        syn::Error::new(
+                            self.pattern.span(),
+                            "every capture needs a name to fill a struct, e.g. `{id:u64}`",
+                        )
+                    })?;
+                    Ok(quote!(#name: #binding))
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            quote!(#target { #(#fields),* })
+        } else {
+            let bindings = captures.iter().map(|(_, binding)| binding);
+            if captures.len() == 1 {
+                quote!(#(#bindings)*)
+            } else {
+                quote!((#(#bindings),*))
+            }
+        };
+
+        let input = &self.input;
+        Ok(quote! {
+            ::aoc::parse::__scan::run(#input, |input| {
+                #(#statements)*
+                ::core::result::Result::Ok(#output)
+            })
+        })
+    }
+}
+
+fn segments(pattern: &LitStr) -> syn::Result<Vec<Segment>> {
+    let error = |message: &str| syn::Error::new(pattern.span(), message);
+
+    let value = pattern.value();
+    let mut chars = value.chars().peekable();
+    let mut ret = Vec::new();
+    let mut literal = String::new();
+    let mut names = Vec::new();
+
+    while let Some(c) = chars.next() {
+        let capture = match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+                continue;
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+                continue;
+            }
+            '}' => return Err(error("unmatched `}`, use `}}` for a literal brace")),
+            ' ' => {
+                while chars.peek() == Some(&' ') {
+                    chars.next();
+                }
+                None
+            }
+            '{' => {
+                let mut inner = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => inner.push(c),
+                        None => return Err(error("unmatched `{`, use `{{` for a literal brace")),
+                    }
+                }
+                Some(capture(&inner, pattern.span()).map_err(|e| error(&e))?)
+            }
+            c => {
+                literal.push(c);
+                continue;
+            }
+        };
+
+        if !literal.is_empty() {
+            ret.push(Segment::Literal(std::mem::take(&mut literal)));
+        }
+        match capture {
+            Some(capture) => {
+                if let Segment::Capture {
+                    name: Some(name), ..
+                } = &capture
+                {
+                    if names.contains(name) {
+                        return Err(error(&format!("duplicate capture `{name}`")));
+                    }
+                    names.push(name.clone());
+                }
+                ret.push(capture);
+            }
+            None => ret.push(Segment::Spaces),
+        }
+    }
+    if !literal.is_empty() {
+        ret.push(Segment::Literal(literal));
+    }
+
+    Ok(ret)
+}
+
+// `{}`, `{type}` or `{name:type}`
+fn capture(inner: &str, span: Span) -> Result<Segment, String> {
+    let (name, kind) = match inner.split_once(':') {
+        Some((name, kind)) if !kind.starts_with(':') => {
+            let name = syn::parse_str::<Ident>(name.trim())
+                .map_err(|_| format!("invalid capture name `{}`", name.trim()))?;
+            (Some(Ident::new(&name.to_string(), span)), kind.trim())
+        }
+        _ => (None, inner.trim()),
+    };
+
+    let kind = match kind {
+        "" | "_" => Kind::Infer,
+        "rest" => Kind::Rest,
+        "word" => Kind::Word,
+        kind => syn::parse_str::<Type>(kind)
+            .map(|v| Kind::Type(Box::new(v)))
+            .map_err(|_| format!("invalid capture type `{kind}`"))?,
+    };
+
+    Ok(Segment::Capture { name, kind })
+}
@@ -0,0 +1,200 @@
+use std::{
+    num::NonZeroUsize,
+    ops::{Index, IndexMut},
+    thread,
+};
+
+use nalgebra::Vector2;
+
+use crate::{Direction, Grid};
+
+/// A cell and its 8 neighbors, see [`Grid::windows3x3`].
+#[derive(Debug)]
+pub struct Neighborhood<'a, T> {
+    grid: &'a Grid<T>,
+    pos: Vector2<usize>,
+}
+
+impl<T> Clone for Neighborhood<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Neighborhood<'_, T> {}
+
+impl<'a, T> Neighborhood<'a, T> {
+    #[must_use]
+    pub fn pos(&self) -> Vector2<usize> {
+        self.pos
+    }
+
+    #[must_use]
+    pub fn center(&self) -> &'a T {
+        &self.grid[self.pos]
+    }
+
+    /// `offset` components are in `-1..=1`, `None` past the edge of the grid.
+    #[must_use]
+    pub fn get(&self, offset: Vector2<isize>) -> Option<&'a T> {
+        self.grid.get_offset(self.pos, offset)
+    }
+
+    #[must_use]
+    pub fn get_dir(&self, dir: Direction) -> Option<&'a T> {
+        self.grid.get_dir(self.pos, dir)
+    }
+
+    /// The neighbors that exist, the center isn't included.
+    pub fn neighbors(&self) -> impl Iterator<Item = &'a T> + 'a {
+        let Self { grid, pos } = *self;
+        Direction::ALL
+            .iter()
+            .filter_map(move |dir| grid.get_dir(pos, *dir))
+    }
+
+    pub fn count<P>(&self, mut pred: P) -> usize
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.neighbors().filter(|v| pred(v)).count()
+    }
+}
+
+impl<T> Grid<T> {
+    /// The 3x3 neighborhood of every cell, in row-major order.
+    pub fn windows3x3(&self) -> impl Iterator<Item = Neighborhood<'_, T>> {
+        self.positions().map(|pos| Neighborhood { grid: self, pos })
+    }
+
+    /// Replaces every cell with `rule` applied to its neighborhood in the
+    /// current generation. Use [`Automaton`] to run many generations.
+    pub fn step_automaton<F>(&mut self, mut rule: F)
+    where
+        F: FnMut(&Neighborhood<T>) -> T,
+    {
+        self.data = self.windows3x3().map(|v| rule(&v)).collect();
+    }
+}
+
+/// Runs generations of a cellular automaton without allocating a new grid for
+/// each one.
+#[derive(Clone, Debug)]
+pub struct Automaton<T> {
+    grid: Grid<T>,
+    buffer: Box<[T]>,
+    pinned: Vec<usize>,
+}
+
+impl<T: Clone> Automaton<T> {
+    #[must_use]
+    pub fn new(grid: Grid<T>) -> Self {
+        let buffer = grid.data.clone();
+
+        Self {
+            grid,
+            buffer,
+            pinned: Vec::new(),
+        }
+    }
+
+    /// The cell at `pos` keeps its current value in every generation, returns
+    /// `false` if `pos` is out of bounds.
+    pub fn pin(&mut self, pos: Vector2<usize>) -> bool {
+        let Some(i) = self.grid.index_of(pos) else {
+            return false;
+        };
+        if !self.pinned.contains(&i) {
+            self.pinned.push(i);
+        }
+
+        true
+    }
+
+    #[must_use]
+    pub fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    /// Changes made here are seen by the next generation. Only single cells
+    /// can be changed so the grid keeps the size of the buffer.
+    #[must_use]
+    pub fn get_mut(&mut self, pos: Vector2<usize>) -> Option<&mut T> {
+        self.grid.get_mut(pos)
+    }
+
+    #[must_use]
+    pub fn into_grid(self) -> Grid<T> {
+        self.grid
+    }
+
+    pub fn step<F>(&mut self, mut rule: F)
+    where
+        F: FnMut(&Neighborhood<T>) -> T,
+    {
+        for (v, neighborhood) in self.buffer.iter_mut().zip(self.grid.windows3x3()) {
+            *v = rule(&neighborhood);
+        }
+
+        self.swap();
+    }
+
+    /// Like [`Automaton::step`], with the rows split between all available
+    /// threads.
+    pub fn step_parallel<F>(&mut self, rule: F)
+    where
+        T: Send + Sync,
+        F: Fn(&Neighborhood<T>) -> T + Sync,
+    {
+        let width = self.grid.size.x.max(1);
+        let threads = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+        let chunk = self.grid.size.y.div_ceil(threads).max(1) * width;
+
+        let grid = &self.grid;
+        let rule = &rule;
+        thread::scope(|scope| {
+            for (n, cells) in self.buffer.chunks_mut(chunk).enumerate() {
+                scope.spawn(move || {
+                    for (i, v) in cells.iter_mut().enumerate() {
+                        let i = n * chunk + i;
+                        let pos = Vector2::new(i % width, i / width);
+                        *v = rule(&Neighborhood { grid, pos });
+                    }
+                });
+            }
+        });
+
+        self.swap();
+    }
+
+    pub fn run<F>(&mut self, generations: usize, mut rule: F)
+    where
+        F: FnMut(&Neighborhood<T>) -> T,
+    {
+        for _ in 0..generations {
+            self.step(&mut rule);
+        }
+    }
+
+    fn swap(&mut self) {
+        for i in &self.pinned {
+            self.buffer[*i] = self.grid.data[*i].clone();
+        }
+
+        std::mem::swap(&mut self.grid.data, &mut self.buffer);
+    }
+}
+
+impl<T> Index<Vector2<usize>> for Automaton<T> {
+    type Output = T;
+
+    fn index(&self, pos: Vector2<usize>) -> &Self::Output {
+        &self.grid[pos]
+    }
+}
+
+impl<T> IndexMut<Vector2<usize>> for Automaton<T> {
+    fn index_mut(&mut self, pos: Vector2<usize>) -> &mut Self::Output {
+        &mut self.grid[pos]
+    }
+}
@@ -1,12 +1,17 @@
 use anyhow::{bail, Context, Result};
+use aoc::input::{self, TrailingNewline};
 
 fn main() -> Result<()> {
-    let input = include_bytes!("../../assets/2015/01.txt");
+    let input = input::load(
+        "assets/2015/01.txt",
+        include_str!("../../assets/2015/01.txt"),
+        TrailingNewline::Trim,
+    );
 
     let mut silver = 0_i64;
     let mut gold = None;
 
-    for (v, i) in input.iter().zip(1..) {
+    for (v, i) in input.bytes().zip(1..) {
         match v {
             b'(' => silver += 1,
             b')' => silver -= 1,
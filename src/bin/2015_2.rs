@@ -1,16 +1,17 @@
 use std::str::FromStr;
 
-use anyhow::{anyhow, Error, Result};
+use anyhow::Result;
+use aoc::{scan, ParseError};
 use tap::prelude::*;
-use winnow::{ascii::dec_uint, error::ContextError, Parser};
 
 fn main() -> Result<()> {
     let input = include_str!("../../assets/2015/02.txt");
 
     let mut silver = 0;
     let mut gold = 0;
-    for line in input.lines() {
-        let dimensions = Dimensions::from_str(line)?;
+    for (i, line) in input.lines().enumerate() {
+        let dimensions = Dimensions::from_str(line)
+            .map_err(|e| e.with_file("assets/2015/02.txt").with_line_offset(i))?;
 
         silver += dimensions.silver();
         gold += dimensions.gold();
@@ -48,16 +49,9 @@ impl Dimensions {
 }
 
 impl FromStr for Dimensions {
-    type Err = Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        (dec_uint::<_, _, ContextError>, 'x', dec_uint, 'x', dec_uint)
-            .parse(s)
-            .map(|(length, _, width, _, height)| Dimensions {
-                l: length,
-                w: width,
-                h: height,
-            })
-            .map_err(|e| anyhow!("\n{e}"))
+        scan!(s, "{l:u64}x{w:u64}x{h:u64}" => Dimensions)
     }
 }
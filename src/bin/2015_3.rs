@@ -1,35 +1,42 @@
-use std::collections::HashSet;
-
+use aoc::{
+    input::{self, TrailingNewline},
+    Direction, SparseGrid,
+};
+use nalgebra::Vector2;
 use tap::prelude::*;
 use thiserror::Error;
 
 fn main() -> anyhow::Result<()> {
-    let input = include_bytes!("../../assets/2015/03.txt");
+    let input = input::load(
+        "assets/2015/03.txt",
+        include_str!("../../assets/2015/03.txt"),
+        TrailingNewline::Trim,
+    );
 
-    let mut silver = HashSet::new();
-    let mut silver_santa = Position::default();
+    let mut silver = SparseGrid::new();
+    let mut silver_santa = Vector2::zeros();
 
-    silver.insert(silver_santa);
+    silver.insert(silver_santa, ());
 
-    let mut gold = HashSet::new();
-    let mut gold_santa = Position::default();
-    let mut gold_robo = Position::default();
+    let mut gold = SparseGrid::new();
+    let mut gold_santa = Vector2::zeros();
+    let mut gold_robo = Vector2::zeros();
 
-    gold.insert(gold_santa);
+    gold.insert(gold_santa, ());
 
     let mut robo_turn = false;
-    for v in input {
-        let dir = Direction::try_from(*v)?;
+    for v in input.bytes() {
+        let dir = direction(v)?;
 
-        silver_santa.update(dir);
-        silver.insert(silver_santa);
+        silver_santa = dir.apply(silver_santa);
+        silver.insert(silver_santa, ());
 
         if robo_turn {
-            gold_robo.update(dir);
-            gold.insert(gold_robo);
+            gold_robo = dir.apply(gold_robo);
+            gold.insert(gold_robo, ());
         } else {
-            gold_santa.update(dir);
-            gold.insert(gold_santa);
+            gold_santa = dir.apply(gold_santa);
+            gold.insert(gold_santa, ());
         }
         robo_turn = !robo_turn;
     }
@@ -40,45 +47,16 @@ fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
-#[derive(Hash, Default, Clone, Copy, PartialEq, Eq)]
-struct Position {
-    pub x: i64,
-    pub y: i64,
-}
-
-impl Position {
-    pub fn update(&mut self, dir: Direction) {
-        match dir {
-            Direction::N => self.y += 1,
-            Direction::S => self.y -= 1,
-            Direction::E => self.x += 1,
-            Direction::W => self.x -= 1,
-        }
-    }
-}
+fn direction(value: u8) -> Result<Direction, DirectionTryFromError> {
+    let ret = match value {
+        b'^' => Direction::Up,
+        b'v' => Direction::Down,
+        b'>' => Direction::Right,
+        b'<' => Direction::Left,
+        v => return DirectionTryFromError(v).pipe(Err),
+    };
 
-#[derive(Clone, Copy)]
-enum Direction {
-    N,
-    S,
-    E,
-    W,
-}
-
-impl TryFrom<u8> for Direction {
-    type Error = DirectionTryFromError;
-
-    fn try_from(value: u8) -> Result<Self, Self::Error> {
-        let ret = match value {
-            b'^' => Self::N,
-            b'v' => Self::S,
-            b'>' => Self::E,
-            b'<' => Self::W,
-            v => return DirectionTryFromError(v).pipe(Err),
-        };
-
-        Ok(ret)
-    }
+    Ok(ret)
 }
 
 #[derive(Debug, Error)]
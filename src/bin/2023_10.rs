@@ -1,9 +1,8 @@
 #![warn(clippy::pedantic)]
 
-use aoc::Grid;
+use aoc::{Grid, Tile};
 use itertools::Itertools;
 use nalgebra::Vector2;
-use tap::Pipe as _;
 
 fn main() {
     let input = include_str!("../../assets/2023/10.txt");
@@ -28,23 +27,34 @@ pub enum Pipe {
     DownLeft,
 }
 
-impl TryFrom<u8> for Pipe {
-    type Error = ();
+impl Tile for Pipe {
+    fn to_char(&self) -> char {
+        match self {
+            Self::None => '.',
+            Self::Start => 'S',
+            Self::Horizontal => '-',
+            Self::Vertical => '|',
+            Self::UpRight => 'L',
+            Self::UpLeft => 'J',
+            Self::DownRight => 'F',
+            Self::DownLeft => '7',
+        }
+    }
 
-    fn try_from(value: u8) -> Result<Self, Self::Error> {
-        let ret = match value {
-            b'.' => Self::None,
-            b'S' => Self::Start,
-            b'-' => Self::Horizontal,
-            b'|' => Self::Vertical,
-            b'L' => Self::UpRight,
-            b'J' => Self::UpLeft,
-            b'F' => Self::DownRight,
-            b'7' => Self::DownLeft,
-            _ => return Err(()),
+    fn from_char(c: char) -> Option<Self> {
+        let ret = match c {
+            '.' => Self::None,
+            'S' => Self::Start,
+            '-' => Self::Horizontal,
+            '|' => Self::Vertical,
+            'L' => Self::UpRight,
+            'J' => Self::UpLeft,
+            'F' => Self::DownRight,
+            '7' => Self::DownLeft,
+            _ => return None,
         };
 
-        Ok(ret)
+        Some(ret)
     }
 }
 
@@ -54,32 +64,17 @@ pub struct Parsed {
 }
 
 fn parse(input: &str) -> Parsed {
-    let mut start = None;
+    let map = input.parse::<Grid<Pipe>>().unwrap();
 
-    let map = input
-        .lines()
-        .enumerate()
-        .map(|(y, line)| {
-            line.bytes()
-                .enumerate()
-                .map(|(x, v)| {
-                    let direction = Pipe::try_from(v).unwrap();
-                    if matches!(direction, Pipe::Start) {
-                        assert!(start.is_none());
-                        start = Some(Vector2::new(x, y));
-                    }
-                    direction
-                })
-                .collect_vec()
-        })
-        .collect_vec()
-        .pipe(Grid::try_from)
+    let start = map
+        .iter()
+        .filter(|(_, v)| **v == Pipe::Start)
+        .map(|(pos, _)| pos)
+        .exactly_one()
+        .ok()
         .unwrap();
 
-    Parsed {
-        start: start.unwrap(),
-        map,
-    }
+    Parsed { start, map }
 }
 
 fn solve(parsed: &Parsed) -> (u64, u64) {
@@ -1,13 +1,18 @@
 use std::{fmt::Write, str::FromStr};
 
-use anyhow::{anyhow, bail, Context, Result};
+use anyhow::{Context, Result};
+use aoc::{
+    math,
+    parse::{self, labelled, uints},
+};
 use tap::prelude::*;
+use winnow::{ascii::line_ending, combinator::separated_pair};
 
 fn main() -> Result<()> {
     let input = include_str!("../../assets/2023/06.txt");
     let parsed = parse(input)?;
 
-    let part_1 = part_1(&parsed);
+    let part_1 = part_1(&parsed).context("too many ways to win")?;
     println!("1 - `{part_1}`");
 
     let part_2 = part_2(&parsed);
@@ -22,44 +27,33 @@ struct Parsed {
 }
 
 fn parse(input: &str) -> Result<Parsed> {
-    let mut lines = input.lines();
-
-    let times = lines
-        .next()
-        .context("missing line 1")
-        .and_then(|line| parse_line(line, "Time:"))?;
-    let distances = lines
-        .next()
-        .context("missing line 2")
-        .and_then(|line| parse_line(line, "Distance:"))?;
-
-    if lines.next().is_some() {
-        bail!("trailing input")
-    }
+    let (times, distances) = parse::parse(
+        input,
+        separated_pair(
+            labelled("Time", uints),
+            line_ending,
+            labelled("Distance", uints),
+        ),
+    )?;
 
     Ok(Parsed { times, distances })
 }
 
-fn part_1(parsed: &Parsed) -> u64 {
+fn part_1(parsed: &Parsed) -> Option<u64> {
     parsed
         .times
         .iter()
         .zip(parsed.distances.iter())
-        .map(|(time, distance)| {
-            (1..*time)
-                .filter_map(|charge| is_beating_map(*time, *distance, charge))
-                .count() as u64
+        .try_fold(1_u64, |acc, (time, distance)| {
+            acc.checked_mul(math::count_products_above(*time, *distance))
         })
-        .product()
 }
 
 fn part_2(parsed: &Parsed) -> u64 {
     let time = concat(&parsed.times);
     let distance = concat(&parsed.distances);
 
-    (1..time)
-        .filter_map(|charge| is_beating_map(time, distance, charge))
-        .count() as u64
+    math::count_products_above(time, distance)
 }
 
 fn concat(src: &[u64]) -> u64 {
@@ -71,24 +65,3 @@ fn concat(src: &[u64]) -> u64 {
         .pipe_as_ref(u64::from_str)
         .unwrap()
 }
-
-fn parse_line(line: &str, prefix: &str) -> Result<Vec<u64>> {
-    line.pipe(|v| {
-        v.strip_prefix(prefix)
-            .ok_or_else(|| anyhow!("invalid prefix, expected `{prefix} ..`, found: `{v}`"))
-    })?
-    .split_ascii_whitespace()
-    .map(|v| u64::from_str(v).map_err(|_| anyhow!("unable to parse: `{v}`")))
-    .collect()
-}
-
-fn is_beating_map(time: u64, distance: u64, charge: u64) -> Option<u64> {
-    let left = time - charge;
-    let new_distance = left * charge;
-
-    if new_distance > distance {
-        Some(new_distance)
-    } else {
-        None
-    }
-}
@@ -1,134 +1,174 @@
-use std::{
-    cmp::Ordering,
-    collections::{BinaryHeap, HashMap},
-    sync::OnceLock,
+use anyhow::{Context, Result};
+use aoc::{
+    cycle::History,
+    graph::LabelledGraph,
+    math,
+    parse::{self, blank_line, ident, lines, pair_entry},
 };
-
 use itertools::Itertools;
-use tap::prelude::*;
+use winnow::{
+    combinator::{repeat, separated_pair},
+    token::one_of,
+    Parser,
+};
 
-fn main() {
+fn main() -> Result<()> {
     let input = include_str!("../../assets/2023/08.txt");
-    let parsed = parse(input);
+    let parsed = parse(input)?;
 
     let part_1 = part_1(&parsed);
     println!("1 - `{part_1}`");
 
-    let part_2 = part_2(&parsed);
+    let part_2 = part_2(&parsed).context("the ghosts never all stand on `Z`")?;
     println!("2 - `{part_2}`");
+
+    Ok(())
 }
 
 #[derive(Debug)]
 pub struct Parsed<'a> {
     pub instructions: Vec<Instruction>,
-    pub map: HashMap<&'a str, (&'a str, &'a str)>,
+    pub graph: LabelledGraph<&'a str>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum Instruction {
     Left,
     Right,
 }
 
-fn parse(input: &str) -> Parsed {
-    let mut lines = input.lines();
-    let instructions = lines
-        .next()
-        .unwrap()
-        .as_bytes()
-        .iter()
-        .map(|v| match v {
-            b'R' => Instruction::Right,
-            b'L' => Instruction::Left,
-            _ => panic!(),
-        })
-        .collect_vec();
-
-    lines.next().unwrap();
-
-    let mut map = HashMap::new();
-    for line in lines {
-        let (key, rest) = line.split_once(" = (").unwrap();
-        let (left, rest) = rest.split_once(", ").unwrap();
-        let right = rest.strip_suffix(')').unwrap();
-
-        map.insert(key, (left, right));
-    }
-
-    Parsed { instructions, map }
+fn parse(input: &str) -> Result<Parsed<'_>> {
+    let instruction = one_of(['L', 'R']).map(|v| match v {
+        'R' => Instruction::Right,
+        _ => Instruction::Left,
+    });
+
+    let (instructions, nodes) = parse::parse(
+        input,
+        separated_pair(
+            repeat(1.., instruction),
+            blank_line,
+            lines(pair_entry(ident, ident)),
+        ),
+    )?;
+    let graph = nodes
+        .into_iter()
+        .map(|(node, (left, right))| (node, [left, right]))
+        .collect();
+
+    Ok(Parsed {
+        instructions,
+        graph,
+    })
 }
 
 fn part_1(parsed: &Parsed) -> u64 {
-    let mut current = parsed
-        .map
-        .get_key_value("AAA")
-        .unwrap()
-        .pipe(|(a, b)| (*a, *b));
-
-    for (instruction, i) in parsed.instructions.iter().cycle().zip(0..) {
-        if current.0 == "ZZZ" {
-            return i;
-        }
+    let start = parsed.graph.node("AAA").unwrap();
+    let end = parsed.graph.node("ZZZ").unwrap();
 
-        match instruction {
-            Instruction::Left => {
-                current = get_left(&current, &parsed.map);
-            }
-            Instruction::Right => {
-                current = get_right(&current, &parsed.map);
-            }
+    for (current, i) in walk(parsed, start).zip(0..) {
+        if current == end {
+            return i;
         }
     }
 
     0
 }
 
-fn part_2(parsed: &Parsed) -> u64 {
-    parsed
-        .map
-        .keys()
+fn part_2(parsed: &Parsed) -> Option<i128> {
+    let ends = parsed.graph.mask(|v| v.ends_with('Z'));
+    let ghosts = parsed
+        .graph
+        .nodes_where(|v| v.ends_with('A'))
+        .map(|start| Ghost::new(parsed, &ends, start))
+        .collect_vec();
+
+    // before every ghost has entered its cycle only actual hits can match,
+    // the ghost entering last has recorded all of them
+    let last = ghosts.iter().max_by_key(|v| v.cycle_start)?;
+    let cyclic = last.cycle_start;
+    let early = last
+        .hits
+        .iter()
         .copied()
-        .filter(|v| v.ends_with('A'))
-        .fold(1_u64, |acc, key| {
-            let mut current = parsed
-                .map
-                .get_key_value(key)
-                .unwrap()
-                .pipe(|(a, b)| (*a, *b));
-
-            for (instruction, i) in parsed.instructions.iter().cycle().zip(1..) {
-                if current.0.ends_with('Z') {
-                    return num::integer::lcm(acc, i);
-                }
-
-                match instruction {
-                    Instruction::Left => {
-                        current = get_left(&current, &parsed.map);
-                    }
-                    Instruction::Right => {
-                        current = get_right(&current, &parsed.map);
-                    }
-                }
-            }
-
-            0
+        .take_while(|t| *t < cyclic)
+        .find(|t| ghosts.iter().all(|v| v.is_hit(*t)));
+    if let Some(t) = early {
+        return Some(i128::from(t));
+    }
+
+    ghosts
+        .iter()
+        .map(|v| {
+            v.hits
+                .iter()
+                .filter(|t| **t >= v.cycle_start)
+                .map(|t| (i128::from(*t), i128::from(v.period)))
         })
+        .multi_cartesian_product()
+        .filter_map(|congruences| math::crt_at_least(congruences, i128::from(cyclic)))
+        .min()
+}
+
+/// Steps at which a ghost stands on a node ending with `Z`.
+struct Ghost {
+    /// Every hit up to the end of the first pass through the cycle.
+    hits: Vec<u64>,
+    cycle_start: u64,
+    period: u64,
+}
+
+impl Ghost {
+    fn new(parsed: &Parsed, ends: &[bool], start: u32) -> Self {
+        let len = parsed.instructions.len();
+        let history = History::new((start, 0), |(current, i)| {
+            (
+                next(parsed, *current, parsed.instructions[*i]),
+                (i + 1) % len,
+            )
+        });
+
+        let hits = history
+            .states()
+            .iter()
+            .zip(0..)
+            .filter(|((current, _), _)| ends[*current as usize])
+            .map(|(_, t)| t)
+            .collect();
+        let cycle = history.cycle();
+
+        Self {
+            hits,
+            cycle_start: cycle.start() as u64,
+            period: cycle.period() as u64,
+        }
+    }
+
+    fn is_hit(&self, t: u64) -> bool {
+        let t = if t < self.cycle_start {
+            t
+        } else {
+            self.cycle_start + (t - self.cycle_start) % self.period
+        };
+
+        self.hits.binary_search(&t).is_ok()
+    }
 }
 
-fn get_left<'a, 'b, 'c>(
-    current: &(&str, (&str, &str)),
-    map: &HashMap<&'a str, (&'b str, &'c str)>,
-) -> (&'a str, (&'b str, &'c str)) {
-    map.get_key_value(current.1 .0)
-        .unwrap()
-        .pipe(|(a, b)| (*a, *b))
+// nodes visited when following the instructions from `start`, starting with
+// `start` itself
+fn walk<'a>(parsed: &'a Parsed, start: u32) -> impl Iterator<Item = u32> + 'a {
+    let mut instructions = parsed.instructions.iter().cycle();
+
+    std::iter::successors(Some(start), move |current| {
+        Some(next(parsed, *current, *instructions.next()?))
+    })
 }
 
-fn get_right<'a, 'b, 'c>(
-    current: &(&str, (&str, &str)),
-    map: &HashMap<&'a str, (&'b str, &'c str)>,
-) -> (&'a str, (&'b str, &'c str)) {
-    map.get_key_value(current.1 .1)
-        .unwrap()
-        .pipe(|(a, b)| (*a, *b))
+fn next(parsed: &Parsed, current: u32, instruction: Instruction) -> u32 {
+    let successors = parsed.graph.successors(current);
+    match instruction {
+        Instruction::Left => successors[0],
+        Instruction::Right => successors[1],
+    }
 }
@@ -2,66 +2,42 @@
 
 use std::str::FromStr;
 
+use anyhow::{Context, Result};
+use aoc::polynomial::Differences;
 use itertools::Itertools;
-use nonempty_collections::{nev, NEVec, NonEmptyIterator};
-use tap::prelude::*;
 
-fn main() {
+fn main() -> Result<()> {
     let input = include_str!("../../assets/2023/09.txt");
     let parsed = parse(input);
 
-    let (part_2, part_1) = solve(parsed.as_slice());
+    let (part_2, part_1) = solve(parsed.as_slice()).context("extrapolation overflowed")?;
     println!("1 - `{part_1}`");
     println!("2 - `{part_2}`");
+
+    Ok(())
 }
 
-fn parse(input: &str) -> Vec<Vec<i64>> {
+fn parse(input: &str) -> Vec<Vec<i128>> {
     input
         .lines()
         .map(|line| {
             line.split_ascii_whitespace()
-                .map(|v| i64::from_str(v).unwrap())
+                .map(|v| i128::from_str(v).unwrap())
                 .collect_vec()
         })
         .collect_vec()
 }
 
-fn solve(parsed: &[Vec<i64>]) -> (i64, i64) {
-    parsed
-        .iter()
-        .map(|v| extrapolate(v.as_slice()))
-        .reduce(|acc, v| (acc.0 + v.0, acc.1 + v.1))
-        .unwrap()
+fn solve(parsed: &[Vec<i128>]) -> Option<(i128, i128)> {
+    parsed.iter().try_fold((0, 0), |acc: (i128, i128), v| {
+        let (first, last) = extrapolate(v.as_slice())?;
+        Some((acc.0.checked_add(first)?, acc.1.checked_add(last)?))
+    })
 }
 
-fn extrapolate(line: &[i64]) -> (i64, i64) {
-    let mut buf = nev![line
-        .iter()
-        .copied()
-        .collect_vec()
-        .pipe(NEVec::from_vec)
-        .unwrap()];
-
-    loop {
-        if buf.last().iter().all(|v| *v == 0) {
-            break;
-        }
-
-        let tmp = buf
-            .last()
-            .iter()
-            .copied()
-            .into_iter()
-            .tuple_windows()
-            .map(|(a, b)| b - a)
-            .collect_vec();
-
-        buf.push(NEVec::from_vec(tmp).unwrap());
-    }
+fn extrapolate(line: &[i128]) -> Option<(i128, i128)> {
+    let differences = Differences::new(line)?;
+    let len = i128::try_from(line.len()).ok()?;
 
-    buf.into_iter()
-        .rev()
-        .map(|v| (*v.first(), *v.last()))
-        .reduce(|acc, (first, last)| (first - acc.0, acc.1 + last))
-        .unwrap()
+    Some((differences.at(-1)?, differences.at(len)?))
 }
@@ -0,0 +1,296 @@
+use std::{
+    fmt::{self, Display},
+    ops::{BitAndAssign, BitOrAssign, BitXorAssign, Not},
+};
+
+use getset::CopyGetters;
+use nalgebra::Vector2;
+
+use crate::{checked_len, Direction, Grid, GridError};
+
+const BITS: usize = u64::BITS as usize;
+
+/// A [`Grid<bool>`] packed into one bit per cell, every row starts on a new
+/// word. Bits past the end of a row are always unset.
+#[derive(CopyGetters, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    #[getset(get_copy = "pub")]
+    size: Vector2<usize>,
+    stride: usize,
+    data: Box<[u64]>,
+}
+
+impl BitGrid {
+    /// All cells unset, fails under the same conditions as
+    /// [`Grid::new_default`].
+    pub fn new(size: Vector2<usize>) -> Result<Self, GridError> {
+        checked_len(size)?;
+
+        let stride = size.x.div_ceil(BITS);
+        let len = stride
+            .checked_mul(size.y)
+            .ok_or(GridError::TooManyElements)?;
+        let data = vec![0; len].into_boxed_slice();
+
+        Ok(Self { size, stride, data })
+    }
+
+    #[must_use]
+    pub fn contains(&self, pos: Vector2<usize>) -> bool {
+        pos.x < self.size.x && pos.y < self.size.y
+    }
+
+    fn locate(&self, pos: Vector2<usize>) -> Option<(usize, u64)> {
+        if self.contains(pos) {
+            Some((pos.y * self.stride + pos.x / BITS, 1 << (pos.x % BITS)))
+        } else {
+            None
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, pos: Vector2<usize>) -> Option<bool> {
+        self.locate(pos).map(|(i, mask)| self.data[i] & mask != 0)
+    }
+
+    /// Returns the previous value, `None` if `pos` is out of bounds.
+    pub fn set(&mut self, pos: Vector2<usize>, v: bool) -> Option<bool> {
+        let (i, mask) = self.locate(pos)?;
+        let ret = self.data[i] & mask != 0;
+        if v {
+            self.data[i] |= mask;
+        } else {
+            self.data[i] &= !mask;
+        }
+
+        Some(ret)
+    }
+
+    /// Returns the new value, `None` if `pos` is out of bounds.
+    pub fn toggle(&mut self, pos: Vector2<usize>) -> Option<bool> {
+        let (i, mask) = self.locate(pos)?;
+        self.data[i] ^= mask;
+
+        Some(self.data[i] & mask != 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.data.fill(0);
+    }
+
+    #[must_use]
+    pub fn step(&self, pos: Vector2<usize>, dir: Direction) -> Option<Vector2<usize>> {
+        let offset = dir.offset();
+        let x = pos.x.checked_add_signed(offset.x)?;
+        let y = pos.y.checked_add_signed(offset.y)?;
+
+        Some(Vector2::new(x, y)).filter(|pos| self.contains(*pos))
+    }
+
+    #[must_use]
+    pub fn get_dir(&self, pos: Vector2<usize>, dir: Direction) -> Option<bool> {
+        self.step(pos, dir).and_then(|pos| self.get(pos))
+    }
+
+    pub fn neighbors(
+        &self,
+        pos: Vector2<usize>,
+    ) -> impl Iterator<Item = (Vector2<usize>, bool)> + '_ {
+        self.neighbors_in(pos, &Direction::ALL)
+    }
+
+    pub fn neighbors_cardinal(
+        &self,
+        pos: Vector2<usize>,
+    ) -> impl Iterator<Item = (Vector2<usize>, bool)> + '_ {
+        self.neighbors_in(pos, &Direction::CARDINAL)
+    }
+
+    fn neighbors_in(
+        &self,
+        pos: Vector2<usize>,
+        dirs: &'static [Direction],
+    ) -> impl Iterator<Item = (Vector2<usize>, bool)> + '_ {
+        dirs.iter().filter_map(move |dir| {
+            let pos = self.step(pos, *dir)?;
+            self.get(pos).map(|v| (pos, v))
+        })
+    }
+
+    /// Number of set cells among the 8 neighbors of `pos`.
+    #[must_use]
+    pub fn count_neighbors(&self, pos: Vector2<usize>) -> usize {
+        self.neighbors(pos).filter(|(_, v)| *v).count()
+    }
+
+    #[must_use]
+    pub fn count_ones(&self) -> usize {
+        self.data.iter().map(|v| v.count_ones() as usize).sum()
+    }
+
+    /// Number of set cells in every row, top to bottom.
+    pub fn count_ones_rows(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.size.y).map(|y| {
+            self.data[y * self.stride..(y + 1) * self.stride]
+                .iter()
+                .map(|v| v.count_ones() as usize)
+                .sum()
+        })
+    }
+
+    /// Positions of the set cells in row-major order.
+    pub fn ones(&self) -> impl Iterator<Item = Vector2<usize>> + '_ {
+        self.data.iter().enumerate().flat_map(move |(i, word)| {
+            let y = i / self.stride;
+            let x = i % self.stride * BITS;
+            let mut word = *word;
+
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+
+                Some(Vector2::new(x + bit, y))
+            })
+        })
+    }
+
+    /// Every cell moved one step in `dir`, cells pushed over the edge are
+    /// dropped and the vacated ones are unset.
+    #[must_use]
+    pub fn shifted(&self, dir: Direction) -> Self {
+        let offset = dir.offset();
+        let mut ret = Self {
+            size: self.size,
+            stride: self.stride,
+            data: vec![0; self.data.len()].into_boxed_slice(),
+        };
+
+        for y in 0..self.size.y {
+            let Some(src) = y.checked_add_signed(-offset.y).filter(|y| *y < self.size.y) else {
+                continue;
+            };
+
+            let src = &self.data[src * self.stride..(src + 1) * self.stride];
+            let dst = &mut ret.data[y * self.stride..(y + 1) * self.stride];
+            match offset.x {
+                1 => {
+                    let mut carry = 0;
+                    for (dst, src) in dst.iter_mut().zip(src) {
+                        *dst = src << 1 | carry;
+                        carry = src >> (BITS - 1);
+                    }
+                }
+                -1 => {
+                    let mut carry = 0;
+                    for (dst, src) in dst.iter_mut().zip(src).rev() {
+                        *dst = src >> 1 | carry;
+                        carry = src << (BITS - 1);
+                    }
+                }
+                _ => dst.copy_from_slice(src),
+            }
+        }
+        ret.mask_padding();
+
+        ret
+    }
+
+    fn mask_padding(&mut self) {
+        let rem = self.size.x % BITS;
+        if rem == 0 || self.stride == 0 {
+            return;
+        }
+
+        let mask = (1 << rem) - 1;
+        for row in self.data.chunks_exact_mut(self.stride) {
+            if let Some(last) = row.last_mut() {
+                *last &= mask;
+            }
+        }
+    }
+
+    fn zip_with<F>(&mut self, other: &Self, f: F)
+    where
+        F: Fn(&mut u64, u64),
+    {
+        assert_eq!(self.size, other.size, "bit grids of different sizes");
+        for (a, b) in self.data.iter_mut().zip(other.data.iter()) {
+            f(a, *b);
+        }
+    }
+
+    #[must_use]
+    pub fn to_grid(&self) -> Grid<bool> {
+        let data = (0..self.size.y)
+            .flat_map(|y| (0..self.size.x).map(move |x| Vector2::new(x, y)))
+            .map(|pos| self.get(pos).unwrap_or_default())
+            .collect();
+
+        Grid {
+            size: self.size,
+            data,
+        }
+    }
+}
+
+/// Panics if the sizes differ.
+impl BitOrAssign<&BitGrid> for BitGrid {
+    fn bitor_assign(&mut self, rhs: &BitGrid) {
+        self.zip_with(rhs, |a, b| *a |= b);
+    }
+}
+
+/// Panics if the sizes differ.
+impl BitAndAssign<&BitGrid> for BitGrid {
+    fn bitand_assign(&mut self, rhs: &BitGrid) {
+        self.zip_with(rhs, |a, b| *a &= b);
+    }
+}
+
+/// Panics if the sizes differ.
+impl BitXorAssign<&BitGrid> for BitGrid {
+    fn bitxor_assign(&mut self, rhs: &BitGrid) {
+        self.zip_with(rhs, |a, b| *a ^= b);
+    }
+}
+
+impl Not for BitGrid {
+    type Output = Self;
+
+    fn not(mut self) -> Self::Output {
+        for v in &mut self.data {
+            *v = !*v;
+        }
+        self.mask_padding();
+
+        self
+    }
+}
+
+impl From<&Grid<bool>> for BitGrid {
+    fn from(value: &Grid<bool>) -> Self {
+        let stride = value.size.x.div_ceil(BITS);
+        let mut ret = Self {
+            size: value.size,
+            stride,
+            data: vec![0; stride * value.size.y].into_boxed_slice(),
+        };
+
+        for (pos, v) in value.iter() {
+            if *v {
+                ret.set(pos, true);
+            }
+        }
+
+        ret
+    }
+}
+
+impl Display for BitGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.to_grid(), f)
+    }
+}
@@ -0,0 +1,206 @@
+use std::{collections::HashMap, hash::Hash};
+
+use getset::CopyGetters;
+
+/// A sequence `x0, f(x0), f(f(x0)), ..` that starts repeating at index
+/// `start` with period `period`, i.e. `start` is the length of the prefix
+/// before the cycle.
+#[derive(CopyGetters, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    #[getset(get_copy = "pub")]
+    start: usize,
+    #[getset(get_copy = "pub")]
+    period: usize,
+}
+
+impl Cycle {
+    /// The smallest index whose value equals the one at index `n`.
+    #[must_use]
+    pub fn equivalent_index(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.period
+        }
+    }
+}
+
+/// `(prefix_len, cycle_len)`
+impl From<Cycle> for (usize, usize) {
+    fn from(value: Cycle) -> Self {
+        (value.start, value.period)
+    }
+}
+
+/// Floyd's tortoise and hare, `step` must be deterministic and the sequence
+/// has to repeat eventually or this never returns.
+pub fn floyd<S, F>(initial: S, mut step: F) -> Cycle
+where
+    S: PartialEq + Clone,
+    F: FnMut(&S) -> S,
+{
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        hare = step(&hare);
+    }
+
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut period = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        period += 1;
+    }
+
+    Cycle { start, period }
+}
+
+/// Brent's algorithm, same as [`floyd`] but with fewer calls to `step`.
+pub fn brent<S, F>(initial: S, step: F) -> Cycle
+where
+    S: PartialEq + Clone,
+    F: FnMut(&S) -> S,
+{
+    match brent_bounded(initial, usize::MAX, step) {
+        Brent::Found { cycle, .. } => cycle,
+        Brent::Reached(_) => unreachable!("no cycle within `usize::MAX` steps"),
+    }
+}
+
+/// Every state of a sequence up to its first repetition, found by hashing
+/// each state. Uses more memory than [`floyd`] or [`brent`] but calls `step`
+/// only once per state and allows looking up any of them afterwards.
+#[derive(Clone, Debug)]
+pub struct History<S> {
+    states: Vec<S>,
+    cycle: Cycle,
+}
+
+impl<S: Hash + Eq + Clone> History<S> {
+    /// `step` must be deterministic and the sequence has to repeat
+    /// eventually or this never returns.
+    pub fn new<F>(initial: S, mut step: F) -> Self
+    where
+        F: FnMut(&S) -> S,
+    {
+        let mut seen = HashMap::new();
+        let mut states = Vec::new();
+        let mut current = initial;
+
+        loop {
+            if let Some(start) = seen.get(&current) {
+                let cycle = Cycle {
+                    start: *start,
+                    period: states.len() - start,
+                };
+                return Self { states, cycle };
+            }
+
+            let next = step(&current);
+            seen.insert(current.clone(), states.len());
+            states.push(current);
+            current = next;
+        }
+    }
+}
+
+impl<S> History<S> {
+    #[must_use]
+    pub fn cycle(&self) -> Cycle {
+        self.cycle
+    }
+
+    /// The prefix followed by one pass through the cycle, in order.
+    #[must_use]
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+
+    /// State after applying `step` `n` times.
+    #[must_use]
+    pub fn nth(&self, n: usize) -> &S {
+        &self.states[self.cycle.equivalent_index(n)]
+    }
+}
+
+enum Brent<S> {
+    Found { cycle: Cycle, start: S },
+    Reached(S),
+}
+
+// Brent's algorithm, gives up once the state after `limit` steps has been
+// computed
+fn brent_bounded<S, F>(initial: S, limit: usize, mut step: F) -> Brent<S>
+where
+    S: PartialEq + Clone,
+    F: FnMut(&S) -> S,
+{
+    if limit == 0 {
+        return Brent::Reached(initial);
+    }
+
+    let mut power = 1;
+    let mut len = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    let mut steps = 1;
+
+    while tortoise != hare {
+        if steps == limit {
+            return Brent::Reached(hare);
+        }
+
+        if power == len {
+            tortoise = hare.clone();
+            power *= 2;
+            len = 0;
+        }
+        hare = step(&hare);
+        len += 1;
+        steps += 1;
+    }
+
+    let mut tortoise = initial;
+    let mut hare = tortoise.clone();
+    for _ in 0..len {
+        hare = step(&hare);
+    }
+
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Brent::Found {
+        cycle: Cycle { start, period: len },
+        start: tortoise,
+    }
+}
+
+/// State after applying `step` `n` times, skipping ahead once a state repeats.
+/// `step` must be deterministic.
+pub fn simulate<S, F>(initial: S, n: usize, mut step: F) -> S
+where
+    S: PartialEq + Clone,
+    F: FnMut(&S) -> S,
+{
+    match brent_bounded(initial, n, &mut step) {
+        Brent::Found { cycle, start } => {
+            let remaining = cycle.equivalent_index(n) - cycle.start;
+            (0..remaining).fold(start, |acc, _| step(&acc))
+        }
+        Brent::Reached(v) => v,
+    }
+}
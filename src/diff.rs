@@ -0,0 +1,189 @@
+use std::path::Path;
+
+use image::{ImageResult, Rgb, RgbImage};
+use nalgebra::Vector2;
+
+use crate::{
+    render::{self, ImageOptions},
+    Grid, GridError,
+};
+
+const OLD: &str = "\x1b[1;31m";
+const NEW: &str = "\x1b[1;32m";
+const RESET: &str = "\x1b[0m";
+
+/// Cells that differ between two snapshots of the same grid, see
+/// [`Grid::diff`].
+#[derive(Debug)]
+pub struct GridDiff<'a, T> {
+    old: &'a Grid<T>,
+    new: &'a Grid<T>,
+    changed: Vec<Vector2<usize>>,
+}
+
+impl<'a, T> GridDiff<'a, T> {
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.changed.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty()
+    }
+
+    /// Changed positions in row-major order.
+    #[must_use]
+    pub fn positions(&self) -> &[Vector2<usize>] {
+        &self.changed
+    }
+
+    /// Position, old value and new value of every changed cell.
+    pub fn changes(&self) -> impl Iterator<Item = (Vector2<usize>, &'a T, &'a T)> + '_ {
+        self.changed
+            .iter()
+            .map(|pos| (*pos, &self.old[*pos], &self.new[*pos]))
+    }
+
+    /// The new grid with changed cells in bold green.
+    pub fn to_ansi<F>(&self, mut f: F) -> String
+    where
+        F: FnMut(&T) -> char,
+    {
+        let mut ret = String::new();
+        for y in 0..self.new.size.y {
+            if y != 0 {
+                ret.push('\n');
+            }
+            self.push_row(&mut ret, self.new, y, NEW, &mut f);
+        }
+
+        ret
+    }
+
+    /// Old grid on the left with changed cells in bold red, new grid on the
+    /// right with changed cells in bold green.
+    pub fn to_ansi_side_by_side<F>(&self, mut f: F) -> String
+    where
+        F: FnMut(&T) -> char,
+    {
+        let mut ret = String::new();
+        for y in 0..self.new.size.y {
+            if y != 0 {
+                ret.push('\n');
+            }
+            self.push_row(&mut ret, self.old, y, OLD, &mut f);
+            ret.push_str(" | ");
+            self.push_row(&mut ret, self.new, y, NEW, &mut f);
+        }
+
+        ret
+    }
+
+    fn push_row<F>(&self, ret: &mut String, grid: &Grid<T>, y: usize, style: &str, f: &mut F)
+    where
+        F: FnMut(&T) -> char,
+    {
+        for x in 0..grid.size.x {
+            let pos = Vector2::new(x, y);
+            let c = f(&grid[pos]);
+            if self
+                .changed
+                .binary_search_by_key(&(y, x), |v| (v.y, v.x))
+                .is_ok()
+            {
+                ret.push_str(style);
+                ret.push(c);
+                ret.push_str(RESET);
+            } else {
+                ret.push(c);
+            }
+        }
+    }
+
+    /// [`Grid::to_image`] of the new grid with changed cells outlined in
+    /// `highlight`, or filled with it if `scale` is below `3`.
+    pub fn to_image<F>(
+        &self,
+        options: &ImageOptions,
+        color: F,
+        highlight: Rgb<u8>,
+    ) -> ImageResult<RgbImage>
+    where
+        F: FnMut(&T) -> Rgb<u8>,
+    {
+        let scale = options.scale.max(1);
+        let mut image = self.new.to_image(options, color)?;
+
+        for pos in &self.changed {
+            let Some(corner) = render::corner(*pos, scale) else {
+                continue;
+            };
+            if scale < 3 {
+                render::fill(&mut image, corner, scale, highlight);
+            } else {
+                outline(&mut image, corner, scale, highlight);
+            }
+        }
+
+        Ok(image)
+    }
+
+    pub fn save_image<P, F>(
+        &self,
+        path: P,
+        options: &ImageOptions,
+        color: F,
+        highlight: Rgb<u8>,
+    ) -> ImageResult<()>
+    where
+        P: AsRef<Path>,
+        F: FnMut(&T) -> Rgb<u8>,
+    {
+        self.to_image(options, color, highlight)?.save(path)
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// Compares `self` to the later snapshot `new`, both have to be the same
+    /// size.
+    pub fn diff<'a>(&'a self, new: &'a Grid<T>) -> Result<GridDiff<'a, T>, GridError> {
+        if self.size != new.size {
+            return Err(GridError::SizeMismatch {
+                expected: self.size,
+                found: new.size,
+            });
+        }
+
+        let changed = self
+            .iter()
+            .zip(new.data.iter())
+            .filter(|((_, old), new)| old != new)
+            .map(|((pos, _), _)| pos)
+            .collect();
+
+        Ok(GridDiff {
+            old: self,
+            new,
+            changed,
+        })
+    }
+}
+
+fn outline(image: &mut RgbImage, (x, y): (u32, u32), size: u32, color: Rgb<u8>) {
+    let thickness = (size / 8).max(1);
+    for d in 0..size {
+        for t in 0..thickness {
+            for (px, py) in [
+                (x + d, y + t),
+                (x + d, y + size - 1 - t),
+                (x + t, y + d),
+                (x + size - 1 - t, y + d),
+            ] {
+                if let Some(pixel) = image.get_pixel_mut_checked(px, py) {
+                    *pixel = color;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use nalgebra::Vector2;
+
+use crate::{Direction, Grid};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Connectivity {
+    Four,
+    Eight,
+}
+
+impl Connectivity {
+    #[must_use]
+    pub fn directions(self) -> &'static [Direction] {
+        match self {
+            Self::Four => &Direction::CARDINAL,
+            Self::Eight => &Direction::ALL,
+        }
+    }
+}
+
+/// Result of [`Grid::components`], components are numbered from `0`.
+pub struct Components {
+    labels: Grid<Option<usize>>,
+    sizes: Vec<usize>,
+}
+
+impl Components {
+    #[must_use]
+    pub fn labels(&self) -> &Grid<Option<usize>> {
+        &self.labels
+    }
+
+    #[must_use]
+    pub fn label(&self, pos: Vector2<usize>) -> Option<usize> {
+        self.labels.get(pos).copied().flatten()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.sizes.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.sizes.is_empty()
+    }
+
+    /// Number of cells in each component, indexed by label.
+    #[must_use]
+    pub fn sizes(&self) -> &[usize] {
+        &self.sizes
+    }
+
+    /// Number of cell edges of each component that border a cell outside of
+    /// it, or the edge of the grid, indexed by label.
+    #[must_use]
+    pub fn perimeters(&self) -> Vec<usize> {
+        let mut ret = vec![0; self.sizes.len()];
+        for (pos, label) in self.labels.iter() {
+            let Some(label) = *label else {
+                continue;
+            };
+
+            ret[label] += Direction::CARDINAL
+                .iter()
+                .filter(|dir| self.labels.get_dir(pos, **dir) != Some(&Some(label)))
+                .count();
+        }
+
+        ret
+    }
+}
+
+impl<T> Grid<T> {
+    /// Marks every cell reachable from `seeds` through cells for which
+    /// `passable` holds. Seeds that aren't passable are ignored.
+    pub fn flood_fill<S, P>(
+        &self,
+        seeds: S,
+        connectivity: Connectivity,
+        mut passable: P,
+    ) -> Grid<bool>
+    where
+        S: IntoIterator<Item = Vector2<usize>>,
+        P: FnMut(Vector2<usize>, &T) -> bool,
+    {
+        let mut filled = self.map(|_| false);
+
+        let mut stack = Vec::new();
+        for seed in seeds.into_iter().filter(|seed| self.contains(*seed)) {
+            let i = seed.y * self.size.x + seed.x;
+            if !filled.data[i] && passable(seed, &self.data[i]) {
+                filled.data[i] = true;
+                stack.push(seed);
+            }
+        }
+
+        while let Some(pos) = stack.pop() {
+            for dir in connectivity.directions() {
+                let Some(next) = self.step(pos, *dir) else {
+                    continue;
+                };
+
+                let i = next.y * self.size.x + next.x;
+                if !filled.data[i] && passable(next, &self.data[i]) {
+                    filled.data[i] = true;
+                    stack.push(next);
+                }
+            }
+        }
+
+        filled
+    }
+
+    /// [`Grid::flood_fill`] seeded with every border cell, cells left unmarked
+    /// are enclosed.
+    pub fn flood_fill_from_borders<P>(&self, connectivity: Connectivity, passable: P) -> Grid<bool>
+    where
+        P: FnMut(Vector2<usize>, &T) -> bool,
+    {
+        let seeds = self.positions().filter(|pos| self.is_border(*pos));
+        self.flood_fill(seeds, connectivity, passable)
+    }
+
+    /// Labels the connected components formed by the cells for which
+    /// `include` holds, other cells are labelled `None`.
+    pub fn components<P>(&self, connectivity: Connectivity, mut include: P) -> Components
+    where
+        P: FnMut(Vector2<usize>, &T) -> bool,
+    {
+        let mask = self
+            .iter()
+            .map(|(pos, v)| include(pos, v))
+            .collect::<Vec<_>>();
+
+        self.label(connectivity, |a, _, b, _| mask[a] && mask[b])
+    }
+
+    /// Labels the regions of equal, connected cells, every cell gets a label.
+    #[must_use]
+    pub fn regions(&self, connectivity: Connectivity) -> Components
+    where
+        T: PartialEq,
+    {
+        self.label(connectivity, |_, a, _, b| a == b)
+    }
+
+    // `connected` gets the data indices and values of both cells, a cell is
+    // labelled if it's connected to itself
+    fn label<F>(&self, connectivity: Connectivity, mut connected: F) -> Components
+    where
+        F: FnMut(usize, &T, usize, &T) -> bool,
+    {
+        let mut labels = self.map(|_| None);
+        let mut sizes = Vec::new();
+
+        let mut stack = Vec::new();
+        for (start, v) in self.data.iter().enumerate() {
+            if labels.data[start].is_some() || !connected(start, v, start, v) {
+                continue;
+            }
+
+            let label = sizes.len();
+            let mut size = 1;
+            labels.data[start] = Some(label);
+            stack.push(start);
+
+            while let Some(i) = stack.pop() {
+                let pos = Vector2::new(i % self.size.x, i / self.size.x);
+                for dir in connectivity.directions() {
+                    let Some(next) = self.step(pos, *dir) else {
+                        continue;
+                    };
+
+                    let j = next.y * self.size.x + next.x;
+                    if labels.data[j].is_none() && connected(i, &self.data[i], j, &self.data[j]) {
+                        labels.data[j] = Some(label);
+                        size += 1;
+                        stack.push(j);
+                    }
+                }
+            }
+
+            sizes.push(size);
+        }
+
+        Components { labels, sizes }
+    }
+}
@@ -0,0 +1,160 @@
+use getset::CopyGetters;
+use nalgebra::Vector2;
+use num::Integer;
+use thiserror::Error;
+
+use crate::Direction;
+
+/// Measurements of a closed lattice polygon, the last vertex connects back to
+/// the first one. Pick's theorem only holds for simple polygons.
+#[derive(CopyGetters, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Polygon {
+    /// Twice the signed area, positive if the vertices go counterclockwise
+    /// with `y` pointing up (clockwise in [`Grid`](crate::Grid) coordinates).
+    #[getset(get_copy = "pub")]
+    double_signed_area: i128,
+    /// Number of lattice points on the boundary, for axis-aligned edges also
+    /// its length.
+    #[getset(get_copy = "pub")]
+    boundary: i128,
+}
+
+impl Polygon {
+    pub fn from_points<I>(points: I) -> Result<Self, GeometryError>
+    where
+        I: IntoIterator<Item = Vector2<i64>>,
+    {
+        let mut iter = points
+            .into_iter()
+            .map(|v| Vector2::new(i128::from(v.x), i128::from(v.y)));
+
+        let first = iter.next().ok_or(GeometryError::Degenerate)?;
+
+        let mut acc = Accumulator::new(first);
+        for point in iter {
+            acc.push(point)?;
+        }
+
+        acc.finish()
+    }
+
+    /// Polygon traced by walking `steps` from the origin, e.g. a dig plan. The
+    /// walk has to end at the origin again.
+    pub fn from_steps<I>(steps: I) -> Result<Self, GeometryError>
+    where
+        I: IntoIterator<Item = (Direction, u64)>,
+    {
+        let mut current = Vector2::new(0_i128, 0_i128);
+        let mut acc = Accumulator::new(current);
+
+        for (dir, len) in steps {
+            let offset = dir.offset();
+            let len = i128::from(len);
+
+            current.x = (offset.x as i128)
+                .checked_mul(len)
+                .and_then(|v| v.checked_add(current.x))
+                .ok_or(GeometryError::Overflow)?;
+            current.y = (offset.y as i128)
+                .checked_mul(len)
+                .and_then(|v| v.checked_add(current.y))
+                .ok_or(GeometryError::Overflow)?;
+
+            acc.push(current)?;
+        }
+
+        if current != Vector2::zeros() {
+            return Err(GeometryError::NotClosed { end: current });
+        }
+
+        acc.finish()
+    }
+
+    pub fn double_area(&self) -> Result<i128, GeometryError> {
+        self.double_signed_area
+            .checked_abs()
+            .ok_or(GeometryError::Overflow)
+    }
+
+    /// Number of lattice points strictly inside, by Pick's theorem.
+    pub fn interior(&self) -> Result<i128, GeometryError> {
+        self.double_area()?
+            .checked_sub(self.boundary)
+            .map(|v| v / 2)
+            .and_then(|v| v.checked_add(1))
+            .ok_or(GeometryError::Overflow)
+    }
+
+    /// Interior and boundary lattice points, e.g. the cells dug out by a dig
+    /// plan.
+    pub fn enclosed(&self) -> Result<i128, GeometryError> {
+        self.interior()?
+            .checked_add(self.boundary)
+            .ok_or(GeometryError::Overflow)
+    }
+}
+
+struct Accumulator {
+    first: Vector2<i128>,
+    previous: Vector2<i128>,
+    double_signed_area: i128,
+    boundary: i128,
+}
+
+impl Accumulator {
+    fn new(first: Vector2<i128>) -> Self {
+        Self {
+            first,
+            previous: first,
+            double_signed_area: 0,
+            boundary: 0,
+        }
+    }
+
+    fn push(&mut self, point: Vector2<i128>) -> Result<(), GeometryError> {
+        let a = self.previous;
+
+        let cross =
+            a.x.checked_mul(point.y)
+                .zip(point.x.checked_mul(a.y))
+                .and_then(|(l, r)| l.checked_sub(r))
+                .ok_or(GeometryError::Overflow)?;
+        self.double_signed_area = self
+            .double_signed_area
+            .checked_add(cross)
+            .ok_or(GeometryError::Overflow)?;
+
+        let dx = point.x.checked_sub(a.x).ok_or(GeometryError::Overflow)?;
+        let dy = point.y.checked_sub(a.y).ok_or(GeometryError::Overflow)?;
+        self.boundary = self
+            .boundary
+            .checked_add(dx.gcd(&dy))
+            .ok_or(GeometryError::Overflow)?;
+
+        self.previous = point;
+
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<Polygon, GeometryError> {
+        self.push(self.first)?;
+        if self.double_signed_area == 0 {
+            return Err(GeometryError::Degenerate);
+        }
+
+        Ok(Polygon {
+            double_signed_area: self.double_signed_area,
+            boundary: self.boundary,
+        })
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum GeometryError {
+    #[error("arithmetic overflow")]
+    Overflow,
+    #[error("polygon has zero area")]
+    Degenerate,
+    #[error("walk ends at {end:?} instead of the origin")]
+    NotClosed { end: Vector2<i128> },
+}
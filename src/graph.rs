@@ -0,0 +1,194 @@
+use std::{borrow::Borrow, collections::HashMap, hash::Hash};
+
+/// Maps labels to dense ids, starting at `0` in the order they were first
+/// seen.
+#[derive(Clone, Debug)]
+pub struct Interner<K> {
+    ids: HashMap<K, u32>,
+    labels: Vec<K>,
+}
+
+impl<K> Default for Interner<K> {
+    fn default() -> Self {
+        Self {
+            ids: HashMap::new(),
+            labels: Vec::new(),
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone> Interner<K> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// # Panics
+    ///
+    /// If there are more than `u32::MAX` labels.
+    pub fn intern(&mut self, label: K) -> u32 {
+        if let Some(id) = self.ids.get(&label) {
+            return *id;
+        }
+
+        let id = u32::try_from(self.labels.len()).expect("too many labels");
+        self.ids.insert(label.clone(), id);
+        self.labels.push(label);
+
+        id
+    }
+
+    #[must_use]
+    pub fn get<Q>(&self, label: &Q) -> Option<u32>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.ids.get(label).copied()
+    }
+
+    #[must_use]
+    pub fn resolve(&self, id: u32) -> Option<&K> {
+        self.labels.get(id as usize)
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Ids and labels in id order.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &K)> {
+        (0..).zip(self.labels.iter())
+    }
+
+    /// Ids of the labels for which `pred` holds.
+    pub fn ids_where<'a, P>(&'a self, mut pred: P) -> impl Iterator<Item = u32> + 'a
+    where
+        P: FnMut(&K) -> bool + 'a,
+    {
+        self.iter().filter(move |(_, v)| pred(v)).map(|(id, _)| id)
+    }
+}
+
+/// Directed graph with labelled nodes, stored as adjacency lists of
+/// [`Interner`] ids. Successors keep the order they were added in, so e.g.
+/// the left and right turn of a node map are `successors(id)[0]` and `[1]`.
+#[derive(Clone, Debug)]
+pub struct LabelledGraph<K> {
+    nodes: Interner<K>,
+    edges: Vec<Vec<u32>>,
+}
+
+impl<K> Default for LabelledGraph<K> {
+    fn default() -> Self {
+        Self {
+            nodes: Interner::default(),
+            edges: Vec::new(),
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone> LabelledGraph<K> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the id of the new or existing node.
+    pub fn add_node(&mut self, label: K) -> u32 {
+        let id = self.nodes.intern(label);
+        if id as usize == self.edges.len() {
+            self.edges.push(Vec::new());
+        }
+
+        id
+    }
+
+    /// Adds both nodes if needed.
+    pub fn add_edge(&mut self, from: K, to: K) -> (u32, u32) {
+        let from = self.add_node(from);
+        let to = self.add_node(to);
+        self.edges[from as usize].push(to);
+
+        (from, to)
+    }
+
+    #[must_use]
+    pub fn nodes(&self) -> &Interner<K> {
+        &self.nodes
+    }
+
+    #[must_use]
+    pub fn node<Q>(&self, label: &Q) -> Option<u32>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.nodes.get(label)
+    }
+
+    #[must_use]
+    pub fn label(&self, id: u32) -> Option<&K> {
+        self.nodes.resolve(id)
+    }
+
+    /// Panics if `id` isn't a node of this graph.
+    #[must_use]
+    pub fn successors(&self, id: u32) -> &[u32] {
+        &self.edges[id as usize]
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Ids of the nodes whose label satisfies `pred`, e.g. the ones ending
+    /// with `A`.
+    pub fn nodes_where<'a, P>(&'a self, pred: P) -> impl Iterator<Item = u32> + 'a
+    where
+        P: FnMut(&K) -> bool + 'a,
+    {
+        self.nodes.ids_where(pred)
+    }
+
+    /// `pred` for every node, indexed by id, for checks in hot loops.
+    #[must_use]
+    pub fn mask<P>(&self, mut pred: P) -> Vec<bool>
+    where
+        P: FnMut(&K) -> bool,
+    {
+        self.nodes.iter().map(|(_, v)| pred(v)).collect()
+    }
+}
+
+/// Nodes with their successors, e.g. the entries of a node map.
+impl<K, I> FromIterator<(K, I)> for LabelledGraph<K>
+where
+    K: Hash + Eq + Clone,
+    I: IntoIterator<Item = K>,
+{
+    fn from_iter<T: IntoIterator<Item = (K, I)>>(iter: T) -> Self {
+        let mut ret = Self::new();
+        for (from, successors) in iter {
+            let from = ret.add_node(from);
+            for to in successors {
+                let to = ret.add_node(to);
+                ret.edges[from as usize].push(to);
+            }
+        }
+
+        ret
+    }
+}
@@ -0,0 +1,239 @@
+use std::ops::{Index, IndexMut};
+
+use getset::CopyGetters;
+use itertools::Itertools;
+use nalgebra::SVector;
+use tap::prelude::*;
+
+use crate::{checked_len, Grid, GridError};
+
+/// A dense grid in `D` dimensions, stored with the first axis varying
+/// fastest. `GridN<T, 2>` has the same layout as [`Grid`] and converts to and
+/// from it for free.
+#[derive(CopyGetters, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GridN<T, const D: usize> {
+    #[getset(get_copy = "pub")]
+    size: SVector<usize, D>,
+    data: Box<[T]>,
+}
+
+pub type Grid3<T> = GridN<T, 3>;
+pub type Grid4<T> = GridN<T, 4>;
+
+impl<T, const D: usize> GridN<T, D> {
+    pub fn new_default(size: SVector<usize, D>) -> Result<Self, GridError>
+    where
+        T: Default,
+    {
+        Self::from_fn(size, |_| T::default())
+    }
+
+    pub fn from_elem(size: SVector<usize, D>, v: T) -> Result<Self, GridError>
+    where
+        T: Clone,
+    {
+        let len = checked_len(size)?;
+
+        Ok(Self {
+            size,
+            data: vec![v; len].into_boxed_slice(),
+        })
+    }
+
+    pub fn from_fn<F>(size: SVector<usize, D>, mut f: F) -> Result<Self, GridError>
+    where
+        F: FnMut(SVector<usize, D>) -> T,
+    {
+        let len = checked_len(size)?;
+        let data = (0..len)
+            .map(|i| f(position_of(size, i)))
+            .collect_vec()
+            .into_boxed_slice();
+
+        Ok(Self { size, data })
+    }
+
+    /// `data` with the first axis varying fastest.
+    pub fn from_vec(size: SVector<usize, D>, data: Vec<T>) -> Result<Self, GridError> {
+        let len = checked_len(size)?;
+        if data.len() != len {
+            return Err(GridError::LengthMismatch {
+                expected: len,
+                found: data.len(),
+            });
+        }
+
+        Ok(Self {
+            size,
+            data: data.into_boxed_slice(),
+        })
+    }
+
+    #[must_use]
+    pub fn map<U, F>(&self, mut f: F) -> GridN<U, D>
+    where
+        F: FnMut(&T) -> U,
+    {
+        GridN {
+            size: self.size,
+            data: self.data.iter().map(&mut f).collect(),
+        }
+    }
+
+    #[must_use]
+    pub fn data(&self) -> &[T] {
+        self.data.as_ref()
+    }
+
+    fn index_of(&self, pos: SVector<usize, D>) -> Option<usize> {
+        if !self.contains(pos) {
+            return None;
+        }
+
+        pos.iter()
+            .zip(self.size.iter())
+            .rev()
+            .fold(0, |acc, (v, len)| acc * len + v)
+            .pipe(Some)
+    }
+
+    #[must_use]
+    pub fn get(&self, pos: SVector<usize, D>) -> Option<&T> {
+        self.index_of(pos).and_then(|v| self.data.get(v))
+    }
+
+    #[must_use]
+    pub fn get_mut(&mut self, pos: SVector<usize, D>) -> Option<&mut T> {
+        self.index_of(pos).and_then(|v| self.data.get_mut(v))
+    }
+
+    #[must_use]
+    pub fn contains(&self, pos: SVector<usize, D>) -> bool {
+        pos.iter().zip(self.size.iter()).all(|(v, len)| v < len)
+    }
+
+    /// `pos + offset`, `None` if it falls outside of the grid.
+    #[must_use]
+    pub fn translate(
+        &self,
+        pos: SVector<usize, D>,
+        offset: SVector<isize, D>,
+    ) -> Option<SVector<usize, D>> {
+        let mut ret = pos;
+        for (v, offset) in ret.iter_mut().zip(offset.iter()) {
+            *v = v.checked_add_signed(*offset)?;
+        }
+
+        Some(ret).filter(|pos| self.contains(*pos))
+    }
+
+    #[must_use]
+    pub fn get_offset(&self, pos: SVector<usize, D>, offset: SVector<isize, D>) -> Option<&T> {
+        self.translate(pos, offset).and_then(|pos| self.get(pos))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = SVector<usize, D>> {
+        let size = self.size;
+        (0..self.data.len()).map(move |i| position_of(size, i))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (SVector<usize, D>, &T)> {
+        self.positions().zip(self.data.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (SVector<usize, D>, &mut T)> {
+        self.positions().zip(self.data.iter_mut())
+    }
+
+    /// The `2 * D` cells sharing a face with `pos`.
+    pub fn neighbors_face(
+        &self,
+        pos: SVector<usize, D>,
+    ) -> impl Iterator<Item = (SVector<usize, D>, &T)> {
+        self.neighbors_in(pos, face_offsets())
+    }
+
+    /// The `3^D - 1` cells sharing at least a corner with `pos`.
+    pub fn neighbors_full(
+        &self,
+        pos: SVector<usize, D>,
+    ) -> impl Iterator<Item = (SVector<usize, D>, &T)> {
+        self.neighbors_in(pos, full_offsets())
+    }
+
+    fn neighbors_in<'a, I>(
+        &'a self,
+        pos: SVector<usize, D>,
+        offsets: I,
+    ) -> impl Iterator<Item = (SVector<usize, D>, &'a T)>
+    where
+        I: Iterator<Item = SVector<isize, D>> + 'a,
+    {
+        offsets.filter_map(move |offset| {
+            let pos = self.translate(pos, offset)?;
+            self.get(pos).map(|v| (pos, v))
+        })
+    }
+}
+
+/// Unit steps along every axis, negative before positive.
+pub fn face_offsets<const D: usize>() -> impl Iterator<Item = SVector<isize, D>> {
+    (0..D).flat_map(|axis| {
+        [-1, 1].into_iter().map(move |v| {
+            let mut ret = SVector::zeros();
+            ret[axis] = v;
+            ret
+        })
+    })
+}
+
+/// Every offset with components in `-1..=1` except the zero vector.
+pub fn full_offsets<const D: usize>() -> impl Iterator<Item = SVector<isize, D>> {
+    (0..D)
+        .map(|_| -1..=1)
+        .multi_cartesian_product()
+        .map(SVector::from_vec)
+        .filter(|v: &SVector<isize, D>| v.iter().any(|v| *v != 0))
+}
+
+fn position_of<const D: usize>(size: SVector<usize, D>, mut i: usize) -> SVector<usize, D> {
+    let mut ret = SVector::<usize, D>::zeros();
+    for (v, len) in ret.iter_mut().zip(size.iter()) {
+        *v = i % len;
+        i /= len;
+    }
+
+    ret
+}
+
+impl<T, const D: usize> Index<SVector<usize, D>> for GridN<T, D> {
+    type Output = T;
+
+    fn index(&self, pos: SVector<usize, D>) -> &Self::Output {
+        let size = self.size;
+        self.get(pos)
+            .unwrap_or_else(|| panic!("position {pos:?} out of bounds for size {size:?}"))
+    }
+}
+
+impl<T, const D: usize> IndexMut<SVector<usize, D>> for GridN<T, D> {
+    fn index_mut(&mut self, pos: SVector<usize, D>) -> &mut Self::Output {
+        let size = self.size;
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("position {pos:?} out of bounds for size {size:?}"))
+    }
+}
+
+impl<T> From<Grid<T>> for GridN<T, 2> {
+    fn from(value: Grid<T>) -> Self {
+        let Grid { size, data } = value;
+        Self { size, data }
+    }
+}
+
+impl<T> From<GridN<T, 2>> for Grid<T> {
+    fn from(value: GridN<T, 2>) -> Self {
+        let GridN { size, data } = value;
+        Self { size, data }
+    }
+}
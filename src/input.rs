@@ -0,0 +1,114 @@
+//! Puts puzzle inputs into a canonical form before they are parsed: no BOM,
+//! `\n` line endings and a trailing newline according to [`TrailingNewline`].
+
+use std::{
+    borrow::Cow,
+    env,
+    fmt::{self, Display},
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TrailingNewline {
+    /// Leave the end of the input as is.
+    Keep,
+    /// Remove all trailing whitespace, including newlines.
+    Trim,
+    /// Replace trailing whitespace with exactly one newline.
+    #[default]
+    Single,
+}
+
+/// What [`normalize`] changed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Changes {
+    pub bom: bool,
+    /// Number of `\r\n` converted to `\n`.
+    pub crlf: usize,
+    /// Trailing whitespace before and after, if it changed.
+    pub trailing: Option<(String, &'static str)>,
+}
+
+impl Changes {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        !self.bom && self.crlf == 0 && self.trailing.is_none()
+    }
+}
+
+impl Display for Changes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut changes = Vec::new();
+        if self.bom {
+            changes.push("stripped BOM".to_owned());
+        }
+        if self.crlf != 0 {
+            changes.push(format!("converted {} CRLF line endings", self.crlf));
+        }
+        if let Some((before, after)) = &self.trailing {
+            changes.push(format!("replaced trailing {before:?} with {after:?}"));
+        }
+
+        if changes.is_empty() {
+            write!(f, "no changes")
+        } else {
+            write!(f, "{}", changes.join(", "))
+        }
+    }
+}
+
+/// Only allocates if line endings have to be converted or a newline appended.
+#[must_use]
+pub fn normalize(input: &str, trailing: TrailingNewline) -> (Cow<'_, str>, Changes) {
+    let mut changes = Changes::default();
+
+    let input = match input.strip_prefix('\u{feff}') {
+        Some(v) => {
+            changes.bom = true;
+            v
+        }
+        None => input,
+    };
+
+    changes.crlf = input.matches("\r\n").count();
+    let mut ret = if changes.crlf == 0 {
+        Cow::Borrowed(input)
+    } else {
+        Cow::Owned(input.replace("\r\n", "\n"))
+    };
+
+    let after = match trailing {
+        TrailingNewline::Keep => return (ret, changes),
+        TrailingNewline::Trim => "",
+        TrailingNewline::Single if ret.trim_end().is_empty() => "",
+        TrailingNewline::Single => "\n",
+    };
+
+    let len = ret.trim_end().len();
+    if ret[len..] != *after {
+        changes.trailing = Some((ret[len..].to_owned(), after));
+        ret = match ret {
+            Cow::Borrowed(v) if v[len..].starts_with(after) => {
+                Cow::Borrowed(&v[..len + after.len()])
+            }
+            v => Cow::Owned(format!("{}{after}", &v[..len])),
+        };
+    }
+
+    (ret, changes)
+}
+
+/// [`normalize`], printing the changes to stderr for `file` when the binary
+/// was started with `-v` or `--verbose`.
+#[must_use]
+pub fn load<'a>(file: &str, input: &'a str, trailing: TrailingNewline) -> Cow<'a, str> {
+    let (ret, changes) = normalize(input, trailing);
+    if !changes.is_empty() && verbose() {
+        eprintln!("{}: {changes}", file.trim_start_matches("../"));
+    }
+
+    ret
+}
+
+fn verbose() -> bool {
+    env::args().skip(1).any(|v| v == "-v" || v == "--verbose")
+}
@@ -1,20 +1,48 @@
 #![warn(clippy::pedantic)]
 #![allow(clippy::missing_errors_doc)]
 
+pub mod automaton;
+pub mod bit_grid;
+pub mod cycle;
+pub mod diff;
+pub mod flood;
+pub mod geometry;
+pub mod graph;
+pub mod grid_n;
+pub mod input;
+pub mod math;
+pub mod parse;
+pub mod polynomial;
+pub mod ray;
+pub mod recorder;
+pub mod render;
+pub mod search;
+pub mod sparse_grid;
+pub mod text;
 pub mod y2023;
 
-use std::{fmt::Display, path::Path};
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+    path::Path,
+};
 
 use anyhow::Result;
 use getset::CopyGetters;
 use image::{ImageResult, Rgb, RgbImage};
 use itertools::Itertools;
-use nalgebra::Vector2;
+use nalgebra::{SVector, Vector2};
 use tap::prelude::*;
 use thiserror::Error;
 
-//  #[macro_use]
-//  extern crate scan_fmt;
+extern crate self as aoc;
+
+pub use aoc_macros::scan;
+pub use bit_grid::BitGrid;
+pub use grid_n::{Grid3, Grid4, GridN};
+pub use parse::ParseError;
+pub use sparse_grid::{Bounds, SparseGrid};
+pub use text::Tile;
 
 //  pub fn print_results<A, B>(prefix: &str, a: A, b: B)
 //  where
@@ -134,31 +162,46 @@ use thiserror::Error;
 //      };
 //  }
 
+/// Turns the puzzle input into `Type`, which may borrow from the input.
 pub trait Parser {
-    type Type;
+    type Type<'a>;
+    type Error: Into<anyhow::Error>;
+
+    /// Applied by the `main` generated by [`aoc!`].
+    const TRAILING_NEWLINE: input::TrailingNewline = input::TrailingNewline::Single;
+
+    fn parse(input: &str) -> Result<Self::Type<'_>, Self::Error>;
 
-    fn parse(input: &str) -> Result<Self::Type>;
+    /// [`Parser::parse`] with `file` named in a returned [`ParseError`].
+    fn parse_file<'a>(file: &str, input: &'a str) -> Result<Self::Type<'a>> {
+        Self::parse(input).map_err(|e| match e.into().downcast::<ParseError>() {
+            Ok(e) => e.with_file(file.trim_start_matches("../")).into(),
+            Err(e) => e,
+        })
+    }
 }
 
 pub trait Solution
 where
     Self::Ret: Display,
 {
-    type Parsed;
+    type Parsed<'a>;
     type Ret;
 
-    fn solve(parsed: &Self::Parsed) -> Result<Self::Ret>;
+    fn solve(parsed: &Self::Parsed<'_>) -> Result<Self::Ret>;
 }
 
 #[macro_export]
 macro_rules! aoc {
     ($parser:ty, $path:literal, $part_1:ty) => {
         fn main() -> anyhow::Result<()> {
-            let input = include_str!($path);
+            use aoc::{Parser, Solution};
 
-            let parsed = parser::parse(input)?;
+            let input = aoc::input::load($path, include_str!($path), <$parser>::TRAILING_NEWLINE);
 
-            let ret_1 = part_1::solve(&parsed)?;
+            let parsed = <$parser>::parse_file($path, &input)?;
+
+            let ret_1 = <$part_1>::solve(&parsed)?;
             println!("- `{ret_1}`");
 
             Ok(())
@@ -168,9 +211,9 @@ macro_rules! aoc {
         fn main() -> anyhow::Result<()> {
             use aoc::{Parser, Solution};
 
-            let input = include_str!($path);
+            let input = aoc::input::load($path, include_str!($path), <$parser>::TRAILING_NEWLINE);
 
-            let parsed = <$parser>::parse(input)?;
+            let parsed = <$parser>::parse_file($path, &input)?;
 
             let ret_1 = <$part_1>::solve(&parsed)?;
             println!("- `{ret_1}`");
@@ -183,7 +226,88 @@ macro_rules! aoc {
     };
 }
 
-#[derive(CopyGetters)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpRight,
+    UpLeft,
+    DownRight,
+    DownLeft,
+}
+
+impl Direction {
+    pub const CARDINAL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    /// `y` grows downwards, matching the row order of [`Grid`].
+    #[must_use]
+    pub fn offset(self) -> Vector2<isize> {
+        match self {
+            Self::Up => Vector2::new(0, -1),
+            Self::Down => Vector2::new(0, 1),
+            Self::Left => Vector2::new(-1, 0),
+            Self::Right => Vector2::new(1, 0),
+            Self::UpRight => Vector2::new(1, -1),
+            Self::UpLeft => Vector2::new(-1, -1),
+            Self::DownRight => Vector2::new(1, 1),
+            Self::DownLeft => Vector2::new(-1, 1),
+        }
+    }
+
+    #[must_use]
+    pub fn apply(self, pos: Vector2<i64>) -> Vector2<i64> {
+        let offset = self.offset();
+
+        Vector2::new(pos.x + offset.x as i64, pos.y + offset.y as i64)
+    }
+
+    #[must_use]
+    pub fn opposite(self) -> Self {
+        match self {
+            Self::Up => Self::Down,
+            Self::Down => Self::Up,
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+            Self::UpRight => Self::DownLeft,
+            Self::UpLeft => Self::DownRight,
+            Self::DownRight => Self::UpLeft,
+            Self::DownLeft => Self::UpRight,
+        }
+    }
+
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        match self {
+            Self::Up => Self::Right,
+            Self::Right => Self::Down,
+            Self::Down => Self::Left,
+            Self::Left => Self::Up,
+            Self::UpRight => Self::DownRight,
+            Self::DownRight => Self::DownLeft,
+            Self::DownLeft => Self::UpLeft,
+            Self::UpLeft => Self::UpRight,
+        }
+    }
+
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        self.turn_right().opposite()
+    }
+}
+
+#[derive(CopyGetters, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Grid<T> {
     #[getset(get_copy = "pub")]
     size: Vector2<usize>,
@@ -191,27 +315,96 @@ pub struct Grid<T> {
 }
 
 impl<T> Grid<T> {
-    #[must_use]
-    pub fn new_default(size: Vector2<usize>) -> Option<Self>
+    pub fn new_default(size: Vector2<usize>) -> Result<Self, GridError>
     where
         T: Default,
     {
-        size.y
-            .checked_mul(size.x)
-            .and_then(|v| {
-                if isize::try_from(v).is_ok() {
-                    Some(v)
-                } else {
-                    None
-                }
-            })
-            .map(|len| {
-                let data = (0..len)
-                    .map(|_| T::default())
-                    .collect_vec()
-                    .into_boxed_slice();
-                Self { size, data }
-            })
+        Self::from_fn(size, |_| T::default())
+    }
+
+    pub fn from_elem(size: Vector2<usize>, v: T) -> Result<Self, GridError>
+    where
+        T: Clone,
+    {
+        let len = checked_len(size)?;
+
+        Ok(Self {
+            size,
+            data: vec![v; len].into_boxed_slice(),
+        })
+    }
+
+    pub fn from_fn<F>(size: Vector2<usize>, mut f: F) -> Result<Self, GridError>
+    where
+        F: FnMut(Vector2<usize>) -> T,
+    {
+        let len = checked_len(size)?;
+        let data = (0..len)
+            .map(|i| f(Vector2::new(i % size.x, i / size.x)))
+            .collect_vec()
+            .into_boxed_slice();
+
+        Ok(Self { size, data })
+    }
+
+    /// `data` in row-major order.
+    pub fn from_vec(size: Vector2<usize>, data: Vec<T>) -> Result<Self, GridError> {
+        let len = checked_len(size)?;
+        if data.len() != len {
+            return Err(GridError::LengthMismatch {
+                expected: len,
+                found: data.len(),
+            });
+        }
+
+        Ok(Self {
+            size,
+            data: data.into_boxed_slice(),
+        })
+    }
+
+    #[must_use]
+    pub fn map<U, F>(&self, mut f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            size: self.size,
+            data: self.data.iter().map(&mut f).collect(),
+        }
+    }
+
+    #[must_use]
+    pub fn map_with_pos<U, F>(&self, mut f: F) -> Grid<U>
+    where
+        F: FnMut(Vector2<usize>, &T) -> U,
+    {
+        Grid {
+            size: self.size,
+            data: self.iter().map(|(pos, v)| f(pos, v)).collect(),
+        }
+    }
+
+    pub fn zip_with<U, V, F>(&self, other: &Grid<U>, mut f: F) -> Result<Grid<V>, GridError>
+    where
+        F: FnMut(&T, &U) -> V,
+    {
+        if self.size != other.size {
+            return Err(GridError::SizeMismatch {
+                expected: self.size,
+                found: other.size,
+            });
+        }
+
+        Ok(Grid {
+            size: self.size,
+            data: self
+                .data
+                .iter()
+                .zip(other.data.iter())
+                .map(|(a, b)| f(a, b))
+                .collect(),
+        })
     }
 
     #[must_use]
@@ -219,20 +412,106 @@ impl<T> Grid<T> {
         self.data.as_ref()
     }
 
+    fn index_of(&self, pos: Vector2<usize>) -> Option<usize> {
+        if self.contains(pos) {
+            Some(pos.y * self.size.x + pos.x)
+        } else {
+            None
+        }
+    }
+
     #[must_use]
     pub fn get(&self, pos: Vector2<usize>) -> Option<&T> {
-        pos.y
-            .checked_mul(self.size.x)
-            .and_then(|v| v.checked_add(pos.x))
-            .and_then(|v| self.data.get(v))
+        self.index_of(pos).and_then(|v| self.data.get(v))
     }
 
     #[must_use]
     pub fn get_mut(&mut self, pos: Vector2<usize>) -> Option<&mut T> {
-        pos.y
-            .checked_mul(self.size.x)
-            .and_then(|v| v.checked_add(pos.x))
-            .and_then(|v| self.data.get_mut(v))
+        self.index_of(pos).and_then(|v| self.data.get_mut(v))
+    }
+
+    /// `pos + offset`, `None` if it falls outside of the grid.
+    #[must_use]
+    pub fn translate(&self, pos: Vector2<usize>, offset: Vector2<isize>) -> Option<Vector2<usize>> {
+        let x = pos.x.checked_add_signed(offset.x)?;
+        let y = pos.y.checked_add_signed(offset.y)?;
+
+        Some(Vector2::new(x, y)).filter(|pos| self.contains(*pos))
+    }
+
+    #[must_use]
+    pub fn get_offset(&self, pos: Vector2<usize>, offset: Vector2<isize>) -> Option<&T> {
+        self.translate(pos, offset).and_then(|pos| self.get(pos))
+    }
+
+    #[must_use]
+    pub fn get_mut_offset(
+        &mut self,
+        pos: Vector2<usize>,
+        offset: Vector2<isize>,
+    ) -> Option<&mut T> {
+        self.translate(pos, offset)
+            .and_then(|pos| self.get_mut(pos))
+    }
+
+    /// Maps any position onto the grid as if it repeated infinitely in every
+    /// direction, `None` only if the grid is empty.
+    #[must_use]
+    pub fn wrap(&self, pos: Vector2<i64>) -> Option<Vector2<usize>> {
+        let wrap = |v: i64, len: usize| {
+            let len = i128::try_from(len).ok().filter(|v| *v != 0)?;
+            usize::try_from(i128::from(v).rem_euclid(len)).ok()
+        };
+
+        Some(Vector2::new(
+            wrap(pos.x, self.size.x)?,
+            wrap(pos.y, self.size.y)?,
+        ))
+    }
+
+    /// Like [`Grid::translate`], wrapping around the edges instead.
+    #[must_use]
+    pub fn translate_wrapping(
+        &self,
+        pos: Vector2<usize>,
+        offset: Vector2<isize>,
+    ) -> Option<Vector2<usize>> {
+        let wrap = |v: usize, offset: isize, len: usize| {
+            let len = i128::try_from(len).ok().filter(|v| *v != 0)?;
+            let v = i128::try_from(v).ok()? + i128::try_from(offset).ok()?;
+            usize::try_from(v.rem_euclid(len)).ok()
+        };
+
+        Some(Vector2::new(
+            wrap(pos.x, offset.x, self.size.x)?,
+            wrap(pos.y, offset.y, self.size.y)?,
+        ))
+    }
+
+    #[must_use]
+    pub fn get_wrapping(&self, pos: Vector2<i64>) -> Option<&T> {
+        self.wrap(pos).and_then(|pos| self.get(pos))
+    }
+
+    #[must_use]
+    pub fn get_mut_wrapping(&mut self, pos: Vector2<i64>) -> Option<&mut T> {
+        self.wrap(pos).and_then(|pos| self.get_mut(pos))
+    }
+
+    #[must_use]
+    pub fn get_offset_wrapping(&self, pos: Vector2<usize>, offset: Vector2<isize>) -> Option<&T> {
+        self.translate_wrapping(pos, offset)
+            .and_then(|pos| self.get(pos))
+    }
+
+    #[must_use]
+    pub fn get_mut_offset_wrapping(
+        &mut self,
+        pos: Vector2<usize>,
+        offset: Vector2<isize>,
+    ) -> Option<&mut T> {
+        self.translate_wrapping(pos, offset)
+            .and_then(|pos| self.get_mut(pos))
     }
 
     pub fn rows(&self) -> impl Iterator<Item = &[T]> {
@@ -243,6 +522,29 @@ impl<T> Grid<T> {
         self.data.chunks_exact_mut(self.size.x)
     }
 
+    pub fn positions(&self) -> impl Iterator<Item = Vector2<usize>> {
+        let width = self.size.x;
+        (0..self.data.len()).map(move |i| Vector2::new(i % width, i / width))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Vector2<usize>, &T)> {
+        self.positions().zip(self.data.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Vector2<usize>, &mut T)> {
+        self.positions().zip(self.data.iter_mut())
+    }
+
+    #[must_use]
+    pub fn contains(&self, pos: Vector2<usize>) -> bool {
+        pos.x < self.size.x && pos.y < self.size.y
+    }
+
+    #[must_use]
+    pub fn is_border(&self, pos: Vector2<usize>) -> bool {
+        pos.x == 0 || pos.y == 0 || pos.x + 1 == self.size.x || pos.y + 1 == self.size.y
+    }
+
     #[must_use]
     pub fn get_up(&self, pos: Vector2<usize>) -> Option<&T> {
         pos.y
@@ -363,6 +665,43 @@ impl<T> Grid<T> {
         self.get_mut(Vector2::new(x, y))
     }
 
+    #[must_use]
+    pub fn step(&self, pos: Vector2<usize>, dir: Direction) -> Option<Vector2<usize>> {
+        self.translate(pos, dir.offset())
+    }
+
+    #[must_use]
+    pub fn get_dir(&self, pos: Vector2<usize>, dir: Direction) -> Option<&T> {
+        self.step(pos, dir).and_then(|pos| self.get(pos))
+    }
+
+    #[must_use]
+    pub fn get_mut_dir(&mut self, pos: Vector2<usize>, dir: Direction) -> Option<&mut T> {
+        self.step(pos, dir).and_then(|pos| self.get_mut(pos))
+    }
+
+    pub fn neighbors(&self, pos: Vector2<usize>) -> impl Iterator<Item = (Vector2<usize>, &T)> {
+        self.neighbors_in(pos, &Direction::ALL)
+    }
+
+    pub fn neighbors_cardinal(
+        &self,
+        pos: Vector2<usize>,
+    ) -> impl Iterator<Item = (Vector2<usize>, &T)> {
+        self.neighbors_in(pos, &Direction::CARDINAL)
+    }
+
+    fn neighbors_in<'a>(
+        &'a self,
+        pos: Vector2<usize>,
+        dirs: &'static [Direction],
+    ) -> impl Iterator<Item = (Vector2<usize>, &'a T)> {
+        dirs.iter().filter_map(move |dir| {
+            let pos = self.step(pos, *dir)?;
+            self.get(pos).map(|v| (pos, v))
+        })
+    }
+
     pub fn save_image<'a, P>(&'a self, path: P) -> ImageResult<()>
     where
         P: AsRef<Path>,
@@ -379,44 +718,76 @@ impl<T> Grid<T> {
     }
 }
 
+impl<T> Index<Vector2<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Vector2<usize>) -> &Self::Output {
+        let size = self.size;
+        self.get(pos)
+            .unwrap_or_else(|| panic!("position {pos:?} out of bounds for size {size:?}"))
+    }
+}
+
+impl<T> IndexMut<Vector2<usize>> for Grid<T> {
+    fn index_mut(&mut self, pos: Vector2<usize>) -> &mut Self::Output {
+        let size = self.size;
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("position {pos:?} out of bounds for size {size:?}"))
+    }
+}
+
 impl<T> TryFrom<Vec<Vec<T>>> for Grid<T> {
-    type Error = GridTryFromError;
+    type Error = GridError;
 
     fn try_from(value: Vec<Vec<T>>) -> std::prelude::v1::Result<Self, Self::Error> {
         let height = value.len();
-        let width = value.get(0).map_or(0, Vec::len);
-
-        let mut acc = height
-            .checked_mul(width)
-            .and_then(|v| {
-                if isize::try_from(v).is_ok() {
-                    Some(v)
-                } else {
-                    None
-                }
-            })
-            .map(Vec::with_capacity)
-            .ok_or(GridTryFromError::TooManyElements)?;
+        let width = value.first().map_or(0, Vec::len);
+        let size = Vector2::new(width, height);
+
+        let mut acc = checked_len(size).map(Vec::with_capacity)?;
 
         for mut row in value {
             if row.len() != width {
-                return Err(GridTryFromError::InequalRows);
+                return Err(GridError::InequalRows);
             }
             acc.append(&mut row);
         }
 
         Self {
-            size: Vector2::new(width, height),
+            size,
             data: acc.into_boxed_slice(),
         }
         .pipe(Ok)
     }
 }
 
+// number of cells, grids can't be empty and have to be indexable
+pub(crate) fn checked_len<const D: usize>(size: SVector<usize, D>) -> Result<usize, GridError> {
+    if D == 0 || size.iter().any(|v| *v == 0) {
+        return Err(GridError::Empty);
+    }
+
+    size.iter()
+        .try_fold(1_usize, |acc, v| acc.checked_mul(*v))
+        .filter(|v| isize::try_from(*v).is_ok())
+        .ok_or(GridError::TooManyElements)
+}
+
 #[derive(Error, Debug)]
-pub enum GridTryFromError {
+pub enum GridError {
     #[error("too many elements")]
     TooManyElements,
     #[error("rows of inequal length encountered")]
     InequalRows,
+    #[error("empty grid")]
+    Empty,
+    #[error("expected {expected} elements, found {found}")]
+    LengthMismatch { expected: usize, found: usize },
+    #[error("expected a grid of size {expected:?}, found {found:?}")]
+    SizeMismatch {
+        expected: Vector2<usize>,
+        found: Vector2<usize>,
+    },
 }
+
+pub type GridTryFromError = GridError;
@@ -0,0 +1,242 @@
+//! Number theory helpers, everything that can overflow is checked.
+
+use std::ops::RangeInclusive;
+
+use num::{CheckedMul, CheckedSub, Integer};
+
+/// Non-negative gcd, `0` for an empty iterator. `None` if the gcd doesn't
+/// fit, e.g. for `T::MIN` and `0`.
+pub fn gcd_all<T, I>(iter: I) -> Option<T>
+where
+    T: Integer + CheckedSub + Copy,
+    I: IntoIterator<Item = T>,
+{
+    checked_abs(iter.into_iter().fold(T::zero(), signed_gcd))
+}
+
+/// Non-negative lcm, `1` for an empty iterator, `None` on overflow.
+pub fn lcm_all<T, I>(iter: I) -> Option<T>
+where
+    T: Integer + CheckedMul + CheckedSub + Copy,
+    I: IntoIterator<Item = T>,
+{
+    iter.into_iter().try_fold(T::one(), |acc, v| {
+        let v = checked_abs(v)?;
+        if acc.is_zero() || v.is_zero() {
+            return Some(T::zero());
+        }
+        (acc / signed_gcd(acc, v)).checked_mul(&v)
+    })
+}
+
+// Euclid without `abs`, the result is a gcd up to its sign. `T::MIN % -1` is
+// avoided since every remainder by `1` or `-1` is `0`
+fn signed_gcd<T>(mut a: T, mut b: T) -> T
+where
+    T: Integer + CheckedSub + Copy,
+{
+    while !b.is_zero() {
+        let r = if b.is_one() || T::zero().checked_sub(&T::one()) == Some(b) {
+            T::zero()
+        } else {
+            a % b
+        };
+        (a, b) = (b, r);
+    }
+
+    a
+}
+
+fn checked_abs<T>(v: T) -> Option<T>
+where
+    T: Integer + CheckedSub,
+{
+    if v < T::zero() {
+        T::zero().checked_sub(&v)
+    } else {
+        Some(v)
+    }
+}
+
+/// `(g, x, y)` with `a * x + b * y == g`, where `g` is the non-negative gcd of
+/// `a` and `b`. `None` on overflow, which needs `a` or `b` to be `i128::MIN`.
+#[must_use]
+pub fn extended_gcd(a: i128, b: i128) -> Option<(i128, i128, i128)> {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1_i128, 0_i128);
+    let (mut y0, mut y1) = (0_i128, 1_i128);
+
+    while r1 != 0 {
+        let q = r0.checked_div(r1)?;
+        (r0, r1) = (r1, r0.checked_sub(q.checked_mul(r1)?)?);
+        (x0, x1) = (x1, x0.checked_sub(q.checked_mul(x1)?)?);
+        (y0, y1) = (y1, y0.checked_sub(q.checked_mul(y1)?)?);
+    }
+
+    if r0 < 0 {
+        Some((r0.checked_neg()?, x0.checked_neg()?, y0.checked_neg()?))
+    } else {
+        Some((r0, x0, y0))
+    }
+}
+
+/// `x` in `0..modulus` with `a * x ≡ 1`, `None` if `a` and `modulus` aren't
+/// coprime or `modulus` isn't positive.
+#[must_use]
+pub fn mod_inverse(a: i128, modulus: i128) -> Option<i128> {
+    if modulus <= 0 {
+        return None;
+    }
+
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus)?;
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// `base.pow(exp) % modulus`, `None` if `modulus` is `0`.
+#[must_use]
+pub fn mod_pow(base: u64, mut exp: u64, modulus: u64) -> Option<u64> {
+    if modulus == 0 {
+        return None;
+    }
+
+    let modulus = u128::from(modulus);
+    let mut base = u128::from(base) % modulus;
+    let mut ret = 1 % modulus;
+    while exp != 0 {
+        if exp & 1 == 1 {
+            ret = ret * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+
+    u64::try_from(ret).ok()
+}
+
+/// Solves `x ≡ residue (mod modulus)` for every `(residue, modulus)`, the
+/// moduli don't have to be coprime. Returns `(x, lcm)` with `x` in `0..lcm`,
+/// every solution is `x + k * lcm`. `None` if the congruences contradict each
+/// other, a modulus isn't positive or the lcm overflows.
+pub fn crt<I>(congruences: I) -> Option<(i128, i128)>
+where
+    I: IntoIterator<Item = (i128, i128)>,
+{
+    congruences
+        .into_iter()
+        .try_fold((0, 1), |(x, m), (residue, modulus)| {
+            if modulus <= 0 {
+                return None;
+            }
+
+            let (g, p, _) = extended_gcd(m, modulus)?;
+            let diff = residue.rem_euclid(modulus) - x;
+            if diff % g != 0 {
+                return None;
+            }
+
+            // m * k ≡ diff (mod modulus), reduced by g
+            let step = modulus / g;
+            let k = mul_mod((diff / g).rem_euclid(step), p.rem_euclid(step), step);
+            let lcm = m.checked_mul(step)?;
+            let x = x.checked_add(m.checked_mul(k)?)?.rem_euclid(lcm);
+
+            Some((x, lcm))
+        })
+}
+
+/// The smallest solution of [`crt`] that is at least `min`, for congruences
+/// that only hold after an offset, e.g. cycles entered after a prefix.
+pub fn crt_at_least<I>(congruences: I, min: i128) -> Option<i128>
+where
+    I: IntoIterator<Item = (i128, i128)>,
+{
+    let (x, lcm) = crt(congruences)?;
+    if x >= min {
+        return Some(x);
+    }
+
+    let steps = Integer::div_ceil(&min.checked_sub(x)?, &lcm);
+    x.checked_add(steps.checked_mul(lcm)?)
+}
+
+// `a * b % modulus` for `a` and `b` in `0..modulus` by doubling, the sums
+// stay below `2 * i128::MAX`
+fn mul_mod(a: i128, b: i128, modulus: i128) -> i128 {
+    let modulus = modulus.unsigned_abs();
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    let mut ret = 0;
+    while b != 0 {
+        if b & 1 == 1 {
+            ret = (ret + a) % modulus;
+        }
+        a = (a + a) % modulus;
+        b >>= 1;
+    }
+
+    i128::try_from(ret).expect("smaller than the modulus")
+}
+
+/// Integers `x` with `a * x * x + b * x + c > 0`, possibly an empty range.
+/// `None` if `a` isn't negative, i.e. the solutions aren't bounded, or on
+/// overflow.
+#[must_use]
+pub fn quadratic_positive(a: i128, b: i128, c: i128) -> Option<RangeInclusive<i128>> {
+    if a >= 0 {
+        return None;
+    }
+
+    // a * x * x + b * x + c > 0 <=> p(x) = a' * x * x + b' * x + c' < 0
+    let (a, b, c) = (a.checked_neg()?, b.checked_neg()?, c.checked_neg()?);
+    let p = |x: i128| {
+        a.checked_mul(x)?
+            .checked_add(b)?
+            .checked_mul(x)?
+            .checked_add(c)
+    };
+
+    let discriminant = b
+        .checked_mul(b)?
+        .checked_sub(a.checked_mul(c)?.checked_mul(4)?)?;
+    if discriminant <= 0 {
+        return Some(RangeInclusive::new(1, 0));
+    }
+
+    // the roots are (-b -+ sqrt(d)) / 2a, with the integer square root each
+    // bound is off by at most one
+    let sqrt = i128::try_from(discriminant.unsigned_abs().isqrt()).ok()?;
+    let denominator = a.checked_mul(2)?;
+
+    let lo = Integer::div_floor(&b.checked_neg()?.checked_sub(sqrt)?, &denominator);
+    let lo = if p(lo)? < 0 { lo } else { lo + 1 };
+    let hi = Integer::div_ceil(&b.checked_neg()?.checked_add(sqrt)?, &denominator);
+    let hi = if p(hi)? < 0 { hi } else { hi - 1 };
+
+    Some(lo..=hi)
+}
+
+/// Number of ways to split `sum` into `x + (sum - x)` with
+/// `x * (sum - x) > threshold`, e.g. charge times that beat a race record.
+#[must_use]
+pub fn count_products_above(sum: u64, threshold: u64) -> u64 {
+    // same as `quadratic_positive(-1, sum, -threshold)`, but unsigned and
+    // wide enough for every input
+    let (s, d) = (u128::from(sum), u128::from(threshold));
+    let beats = |x: u128| x * (s - x) > d;
+
+    let Some(discriminant) = (s * s).checked_sub(4 * d).filter(|v| *v != 0) else {
+        return 0;
+    };
+
+    // the solutions are symmetric around `s / 2`, the smallest one is the
+    // lower root rounded down or the integer after it
+    let lo = (s - discriminant.isqrt()) / 2;
+    let lo = if beats(lo) { lo } else { lo + 1 };
+    if 2 * lo > s || !beats(lo) {
+        return 0;
+    }
+
+    // `x = 0` never beats, so this is at most `sum - 1`
+    #[allow(clippy::cast_possible_truncation)]
+    let count = (s + 1 - 2 * lo) as u64;
+    count
+}
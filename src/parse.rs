@@ -0,0 +1,347 @@
+//! `winnow` parsers for the input shapes that keep coming back. Every parser
+//! here works on `&str` with [`ContextError`], run them through [`parse`] to
+//! get a [`ParseError`] with a line and column.
+
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fmt::{self, Display},
+    hash::Hash,
+};
+
+use getset::{CopyGetters, Getters};
+use thiserror::Error;
+use winnow::{
+    ascii::{
+        alphanumeric1, dec_int, dec_uint, line_ending, multispace0, space0, space1, Int, Uint,
+    },
+    combinator::{
+        alt, cut_err, delimited, eof, peek, preceded, repeat, separated, separated_pair, terminated,
+    },
+    error::{ContextError, StrContext, StrContextValue},
+    token::{any, none_of},
+    PResult, Parser,
+};
+
+use crate::{Grid, Tile};
+
+/// Runs `parser` over the whole of `input`, trailing whitespace is ignored.
+pub fn parse<'a, O, P>(input: &'a str, parser: P) -> Result<O, ParseError>
+where
+    P: Parser<&'a str, O, ContextError>,
+{
+    terminated(parser, (multispace0, eof.context(expected("end of input"))))
+        .parse(input)
+        .map_err(|e| ParseError::new(input, e.offset(), e.inner()))
+}
+
+pub fn uint<T: Uint>(input: &mut &str) -> PResult<T> {
+    dec_uint
+        .context(expected("unsigned integer"))
+        .parse_next(input)
+}
+
+pub fn int<T: Int>(input: &mut &str) -> PResult<T> {
+    dec_int.context(expected("integer")).parse_next(input)
+}
+
+/// Unsigned integers separated by spaces or tabs.
+pub fn uints<T: Uint>(input: &mut &str) -> PResult<Vec<T>> {
+    separated(1.., uint::<T>, space1).parse_next(input)
+}
+
+/// Signed integers separated by spaces or tabs.
+pub fn ints<T: Int>(input: &mut &str) -> PResult<Vec<T>> {
+    separated(1.., int::<T>, space1).parse_next(input)
+}
+
+/// One or more `item`s separated by `sep`, with optional spaces around `sep`,
+/// e.g. `list(uint, ',')` for `1, 2,3`.
+pub fn list<'a, O, O2, P, S>(item: P, sep: S) -> impl Parser<&'a str, Vec<O>, ContextError>
+where
+    P: Parser<&'a str, O, ContextError>,
+    S: Parser<&'a str, O2, ContextError>,
+{
+    separated(1.., item, (space0, sep, space0))
+}
+
+/// `label: values`, e.g. `labelled("Time", uints)` for `Time:   7  15   30`.
+pub fn labelled<'a, O, P>(label: &'static str, values: P) -> impl Parser<&'a str, O, ContextError>
+where
+    P: Parser<&'a str, O, ContextError>,
+{
+    preceded(
+        (label.context(expected_literal(label)), ':', space0),
+        values,
+    )
+}
+
+/// `item` on every line.
+pub fn lines<'a, O, P>(item: P) -> impl Parser<&'a str, Vec<O>, ContextError>
+where
+    P: Parser<&'a str, O, ContextError>,
+{
+    separated(1.., item, line_ending)
+}
+
+/// The empty line between two sections.
+pub fn blank_line<'a>(input: &mut &'a str) -> PResult<&'a str> {
+    (line_ending, line_ending)
+        .recognize()
+        .context(expected("blank line"))
+        .parse_next(input)
+}
+
+/// One or more sections separated by [`blank_line`].
+pub fn sections<'a, O, P>(section: P) -> impl Parser<&'a str, Vec<O>, ContextError>
+where
+    P: Parser<&'a str, O, ContextError>,
+{
+    separated(1.., section, blank_line)
+}
+
+/// A run of ASCII letters and digits.
+pub fn ident<'a>(input: &mut &'a str) -> PResult<&'a str> {
+    alphanumeric1
+        .context(expected("identifier"))
+        .parse_next(input)
+}
+
+/// `key = (a, b)`
+pub fn pair_entry<'a, K, V, PK, PV>(
+    key: PK,
+    value: PV,
+) -> impl Parser<&'a str, (K, (V, V)), ContextError>
+where
+    PK: Parser<&'a str, K, ContextError>,
+    PV: Parser<&'a str, V, ContextError> + Clone,
+{
+    separated_pair(
+        key,
+        (space0, '=', space0),
+        delimited(
+            '(',
+            separated_pair(value.clone(), (',', space0), value),
+            ')',
+        ),
+    )
+}
+
+/// Lines of [`pair_entry`], e.g. `pair_map(ident, ident)` for the node maps of
+/// 2023 day 8.
+pub fn pair_map<'a, K, V, PK, PV>(
+    key: PK,
+    value: PV,
+) -> impl Parser<&'a str, HashMap<K, (V, V)>, ContextError>
+where
+    K: Eq + Hash,
+    PK: Parser<&'a str, K, ContextError>,
+    PV: Parser<&'a str, V, ContextError> + Clone,
+{
+    separated(1.., pair_entry(key, value), line_ending)
+}
+
+/// Lines of [`Tile`]s up to the end of input or the next blank line.
+pub fn grid<T: Tile>(input: &mut &str) -> PResult<Grid<T>> {
+    let tile = none_of(['\r', '\n']).verify_map(T::from_char);
+    let row = terminated(
+        repeat::<_, _, Vec<_>, _, _>(1.., tile),
+        cut_err(peek(alt((line_ending, eof)))).context(expected("tile")),
+    );
+
+    separated(1.., row, line_ending)
+        .try_map(|rows: Vec<_>| Grid::try_from(rows))
+        .context(StrContext::Label("grid"))
+        .parse_next(input)
+}
+
+/// Values that can be captured by [`scan!`](crate::scan).
+pub trait Scan: Sized {
+    fn scan(input: &mut &str) -> PResult<Self>;
+}
+
+macro_rules! impl_scan {
+    ($parser:ident: $($ty:ty),*) => {
+        $(
+            impl Scan for $ty {
+                fn scan(input: &mut &str) -> PResult<Self> {
+                    $parser(input)
+                }
+            }
+        )*
+    };
+}
+
+impl_scan!(uint: u8, u16, u32, u64, u128);
+impl_scan!(int: i8, i16, i32, i64, i128);
+
+impl Scan for char {
+    fn scan(input: &mut &str) -> PResult<Self> {
+        any.context(expected("character")).parse_next(input)
+    }
+}
+
+impl Scan for String {
+    fn scan(input: &mut &str) -> PResult<Self> {
+        __scan::word(input).map(str::to_owned)
+    }
+}
+
+// used by the code `scan!` expands to
+#[doc(hidden)]
+pub mod __scan {
+    use winnow::{
+        ascii::space1,
+        combinator::rest as rest_,
+        error::{ContextError, StrContext, StrContextValue},
+        token::{literal as literal_, take_till},
+        PResult, Parser,
+    };
+
+    use super::{expected, ParseError};
+
+    pub fn run<'a, O, F>(input: &'a str, f: F) -> Result<O, ParseError>
+    where
+        F: FnMut(&mut &'a str) -> PResult<O>,
+    {
+        super::parse(input, f)
+    }
+
+    pub fn literal<'a>(input: &mut &'a str, literal: &'static str) -> PResult<&'a str> {
+        literal_(literal)
+            .context(StrContext::Expected(StrContextValue::StringLiteral(
+                literal,
+            )))
+            .parse_next(input)
+    }
+
+    pub fn spaces<'a>(input: &mut &'a str) -> PResult<&'a str> {
+        space1.context(expected("whitespace")).parse_next(input)
+    }
+
+    pub fn word<'a>(input: &mut &'a str) -> PResult<&'a str> {
+        take_till(1.., char::is_whitespace)
+            .context(expected("word"))
+            .parse_next(input)
+    }
+
+    pub fn rest<'a>(input: &mut &'a str) -> PResult<&'a str> {
+        rest_::<_, ContextError>.parse_next(input)
+    }
+}
+
+fn expected(description: &'static str) -> StrContext {
+    StrContext::Expected(StrContextValue::Description(description))
+}
+
+fn expected_literal(literal: &'static str) -> StrContext {
+    StrContext::Expected(StrContextValue::StringLiteral(literal))
+}
+
+/// Failure of [`parse`], lines and columns start at `1`, columns count
+/// characters. Displayed with the offending line and a caret under the column.
+#[derive(Error, Getters, CopyGetters, Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    #[getset(get = "pub")]
+    file: Option<String>,
+    #[getset(get_copy = "pub")]
+    line: usize,
+    #[getset(get_copy = "pub")]
+    column: usize,
+    #[getset(get = "pub")]
+    message: String,
+    #[getset(get = "pub")]
+    line_text: String,
+}
+
+impl ParseError {
+    fn new(input: &str, offset: usize, error: &ContextError) -> Self {
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |v| v + 1);
+        let line = before.matches('\n').count() + 1;
+        let column = before[line_start..].chars().count() + 1;
+        let line_text = input[line_start..]
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_owned();
+
+        let message = error.to_string().replace('\n', ", ");
+        let message = if message.is_empty() {
+            "unexpected input".to_owned()
+        } else {
+            message
+        };
+
+        Self {
+            file: None,
+            line,
+            column,
+            message,
+            line_text,
+        }
+    }
+
+    #[must_use]
+    pub fn with_file<S>(self, file: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            file: Some(file.into()),
+            ..self
+        }
+    }
+
+    /// For errors from parsing a single line, `lines` is the number of lines
+    /// before it.
+    #[must_use]
+    pub fn with_line_offset(self, lines: usize) -> Self {
+        Self {
+            line: self.line + lines,
+            ..self
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+
+        writeln!(f, "{}", self.message)?;
+        match &self.file {
+            Some(file) => writeln!(f, "{gutter}--> {file}:{}:{}", self.line, self.column)?,
+            None => writeln!(f, "{gutter}--> {}:{}", self.line, self.column)?,
+        }
+        let (text, caret) = snippet(&self.line_text, self.column - 1);
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{} | {text}", self.line)?;
+        write!(f, "{gutter} | {}^", " ".repeat(caret))
+    }
+}
+
+const SNIPPET_WIDTH: usize = 80;
+
+// at most `SNIPPET_WIDTH` characters of `line` around `column`, with ellipses
+// where it was cut, and the column of the caret within them
+fn snippet(line: &str, column: usize) -> (Cow<'_, str>, usize) {
+    let len = line.chars().count();
+    if len <= SNIPPET_WIDTH {
+        return (Cow::Borrowed(line), column);
+    }
+
+    let end = (column.saturating_sub(SNIPPET_WIDTH / 2) + SNIPPET_WIDTH).min(len);
+    let start = end - SNIPPET_WIDTH;
+
+    let mut text = String::new();
+    if start != 0 {
+        text.push_str("...");
+    }
+    text.extend(line.chars().skip(start).take(SNIPPET_WIDTH));
+    if end != len {
+        text.push_str("...");
+    }
+
+    let caret = column - start + if start == 0 { 0 } else { 3 };
+    (Cow::Owned(text), caret)
+}
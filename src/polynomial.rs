@@ -0,0 +1,99 @@
+//! Predicting further values of sequences that follow a polynomial, exactly
+//! and with overflow reported as `None`.
+
+use getset::Getters;
+use num::{
+    rational::Ratio,
+    traits::{CheckedAdd, CheckedMul},
+};
+
+/// The lowest degree polynomial through `values[i]` at `i`, stored as the
+/// leading entries of its difference table, i.e. in Newton's forward form.
+#[derive(Getters, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Differences {
+    /// `leading[j]` is the first value of the `j`th differences, trailing
+    /// zeroes are dropped.
+    #[getset(get = "pub")]
+    leading: Vec<i128>,
+}
+
+impl Differences {
+    /// `None` if `values` is empty or a difference overflows.
+    #[must_use]
+    pub fn new(values: &[i128]) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+
+        let mut leading = Vec::with_capacity(values.len());
+        let mut row = values.to_vec();
+        while let Some(first) = row.first() {
+            leading.push(*first);
+            row = row
+                .windows(2)
+                .map(|v| v[1].checked_sub(v[0]))
+                .collect::<Option<_>>()?;
+        }
+
+        while leading.last() == Some(&0) {
+            leading.pop();
+        }
+
+        Some(Self { leading })
+    }
+
+    /// `None` for the zero polynomial.
+    #[must_use]
+    pub fn degree(&self) -> Option<usize> {
+        self.leading.len().checked_sub(1)
+    }
+
+    /// Value at `k`, which may lie before the first value or arbitrarily far
+    /// after the last one. `None` on overflow.
+    #[must_use]
+    pub fn at(&self, k: i128) -> Option<i128> {
+        // sum of binomial(k, j) * leading[j], with
+        // binomial(k, j) = binomial(k, j - 1) * (k - j + 1) / j
+        let mut binomial = 1_i128;
+        let mut ret = 0_i128;
+        for (j, v) in (0_i128..).zip(&self.leading) {
+            if j != 0 {
+                binomial = binomial.checked_mul(k.checked_sub(j - 1)?)? / j;
+            }
+            ret = ret.checked_add(binomial.checked_mul(*v)?)?;
+        }
+
+        Some(ret)
+    }
+}
+
+/// Value at `k` of the sequence continuing `values`, see [`Differences`].
+#[must_use]
+pub fn extrapolate(values: &[i128], k: i128) -> Option<i128> {
+    Differences::new(values)?.at(k)
+}
+
+/// Lagrange interpolation of `points` at `x`. Unlike [`extrapolate`] the
+/// points don't have to be evenly spaced. `None` if two points share an `x`
+/// or on overflow.
+#[must_use]
+pub fn interpolate(points: &[(i128, i128)], x: i128) -> Option<Ratio<i128>> {
+    points
+        .iter()
+        .enumerate()
+        .try_fold(Ratio::from_integer(0), |acc, (i, (xi, yi))| {
+            let term = points
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .try_fold(Ratio::from_integer(*yi), |acc, (_, (xj, _))| {
+                    let denominator = xi.checked_sub(*xj)?;
+                    if denominator == 0 {
+                        return None;
+                    }
+                    acc.checked_mul(&Ratio::new(x.checked_sub(*xj)?, denominator))
+                })?;
+
+            acc.checked_add(&term)
+        })
+}
@@ -0,0 +1,71 @@
+use std::iter::FusedIterator;
+
+use nalgebra::Vector2;
+
+use crate::{Direction, Grid};
+
+/// Cells along a straight line, see [`Grid::ray`].
+#[derive(Clone, Debug)]
+pub struct Ray<'a, T> {
+    grid: &'a Grid<T>,
+    pos: Option<Vector2<usize>>,
+    dir: Direction,
+}
+
+impl<'a, T> Iterator for Ray<'a, T> {
+    type Item = (Vector2<usize>, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pos = self.grid.step(self.pos?, self.dir);
+        self.pos = pos;
+
+        pos.map(|pos| (pos, &self.grid[pos]))
+    }
+}
+
+impl<T> FusedIterator for Ray<'_, T> {}
+
+impl<T> Grid<T> {
+    /// Walks from `pos` in `dir` until the edge, `pos` itself isn't included.
+    #[must_use]
+    pub fn ray(&self, pos: Vector2<usize>, dir: Direction) -> Ray<'_, T> {
+        Ray {
+            grid: self,
+            pos: Some(pos).filter(|pos| self.contains(*pos)),
+            dir,
+        }
+    }
+
+    #[must_use]
+    pub fn first_matching<P>(
+        &self,
+        pos: Vector2<usize>,
+        dir: Direction,
+        mut pred: P,
+    ) -> Option<(Vector2<usize>, &T)>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.ray(pos, dir).find(|(_, v)| pred(v))
+    }
+
+    /// Number of directions in which the first cell for which `opaque` holds
+    /// also satisfies `target`, e.g. occupied seats seen over the floor.
+    #[must_use]
+    pub fn count_visible_in_all_directions<O, P>(
+        &self,
+        pos: Vector2<usize>,
+        mut opaque: O,
+        mut target: P,
+    ) -> usize
+    where
+        O: FnMut(&T) -> bool,
+        P: FnMut(&T) -> bool,
+    {
+        Direction::ALL
+            .iter()
+            .filter_map(|dir| self.first_matching(pos, *dir, &mut opaque))
+            .filter(|(_, v)| target(v))
+            .count()
+    }
+}
@@ -0,0 +1,183 @@
+use std::{
+    fs::{self, File},
+    io::BufWriter,
+    marker::PhantomData,
+    path::Path,
+    time::Duration,
+};
+
+use image::{
+    codecs::gif::{GifEncoder, Repeat},
+    error::{ParameterError, ParameterErrorKind},
+    Delay, DynamicImage, Frame, ImageError, ImageResult, Rgb,
+};
+use nalgebra::Vector2;
+
+use crate::{render::ImageOptions, Grid};
+
+/// Collects frames of a changing [`Grid`], only the cells that changed since
+/// the previous frame are stored.
+pub struct GridRecorder<T, F> {
+    color: F,
+    options: ImageOptions,
+    delay: Duration,
+    size: Option<Vector2<usize>>,
+    first: Vec<Rgb<u8>>,
+    last: Vec<Rgb<u8>>,
+    diffs: Vec<Vec<(usize, Rgb<u8>)>>,
+    _cell: PhantomData<fn(&T)>,
+}
+
+impl<T, F> GridRecorder<T, F>
+where
+    F: FnMut(&T) -> Rgb<u8>,
+{
+    /// `color` maps cells to pixels, `options` apply to every frame.
+    pub fn new(color: F, options: ImageOptions, delay: Duration) -> Self {
+        Self {
+            color,
+            options,
+            delay,
+            size: None,
+            first: Vec::new(),
+            last: Vec::new(),
+            diffs: Vec::new(),
+            _cell: PhantomData,
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        if self.size.is_some() {
+            self.diffs.len() + 1
+        } else {
+            0
+        }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.size.is_none()
+    }
+
+    /// Adds a frame, every frame has to be the same size as the first one.
+    pub fn record(&mut self, grid: &Grid<T>) -> ImageResult<()> {
+        let colors = grid.data.iter().map(&mut self.color).collect::<Vec<_>>();
+
+        match self.size {
+            None => {
+                self.size = Some(grid.size);
+                self.first.clone_from(&colors);
+                self.last = colors;
+            }
+            Some(size) if size == grid.size => {
+                let diff = colors
+                    .iter()
+                    .zip(self.last.iter())
+                    .enumerate()
+                    .filter(|(_, (new, old))| new != old)
+                    .map(|(i, (new, _))| (i, *new))
+                    .collect();
+                self.diffs.push(diff);
+                self.last = colors;
+            }
+            Some(_) => return Err(dimension_mismatch()),
+        }
+
+        Ok(())
+    }
+
+    /// Adds a frame that differs from the previous one only in `changes`,
+    /// which must not be the first frame. Nothing is recorded if a position
+    /// is out of bounds.
+    pub fn record_changes<'a, I>(&mut self, changes: I) -> ImageResult<()>
+    where
+        T: 'a,
+        I: IntoIterator<Item = (Vector2<usize>, &'a T)>,
+    {
+        let size = self.size.ok_or_else(no_previous_frame)?;
+
+        let color = &mut self.color;
+        let diff = changes
+            .into_iter()
+            .map(|(pos, v)| {
+                if pos.x >= size.x || pos.y >= size.y {
+                    return Err(dimension_mismatch());
+                }
+                Ok((pos.y * size.x + pos.x, color(v)))
+            })
+            .collect::<ImageResult<Vec<_>>>()?;
+
+        for (i, color) in &diff {
+            self.last[*i] = *color;
+        }
+        self.diffs.push(diff);
+
+        Ok(())
+    }
+
+    fn frames(&self) -> impl Iterator<Item = Grid<Rgb<u8>>> + '_ {
+        let size = self.size.unwrap_or_default();
+        let mut current = self.first.clone();
+
+        self.size
+            .map(|_| self.first.clone())
+            .into_iter()
+            .chain(self.diffs.iter().map(move |diff| {
+                for (i, color) in diff {
+                    current[*i] = *color;
+                }
+                current.clone()
+            }))
+            .map(move |data| Grid {
+                size,
+                data: data.into_boxed_slice(),
+            })
+    }
+
+    /// Writes an endlessly looping GIF.
+    pub fn save_gif<P>(&self, path: P) -> ImageResult<()>
+    where
+        P: AsRef<Path>,
+    {
+        let mut encoder = GifEncoder::new(BufWriter::new(File::create(path)?));
+        encoder.set_repeat(Repeat::Infinite)?;
+
+        let delay = Delay::from_saturating_duration(self.delay);
+        for grid in self.frames() {
+            let image = grid.to_image(&self.options, |v| *v)?;
+            let image = DynamicImage::ImageRgb8(image).into_rgba8();
+            encoder.encode_frame(Frame::from_parts(image, 0, 0, delay))?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes every frame to `dir` as `00000.png`, `00001.png`, ..
+    pub fn save_frames<P>(&self, dir: P) -> ImageResult<()>
+    where
+        P: AsRef<Path>,
+    {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+
+        for (n, grid) in self.frames().enumerate() {
+            grid.to_image(&self.options, |v| *v)?
+                .save(dir.join(format!("{n:05}.png")))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn dimension_mismatch() -> ImageError {
+    ImageError::Parameter(ParameterError::from_kind(
+        ParameterErrorKind::DimensionMismatch,
+    ))
+}
+
+fn no_previous_frame() -> ImageError {
+    ImageError::Parameter(ParameterError::from_kind(ParameterErrorKind::Generic(
+        "no frame to apply changes to, `record` the first one".to_owned(),
+    )))
+}
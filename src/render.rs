@@ -0,0 +1,331 @@
+use std::{fmt::Write as _, fs, path::Path};
+
+use image::{
+    error::{LimitError, LimitErrorKind},
+    ImageError, ImageResult, Rgb, RgbImage,
+};
+use nalgebra::Vector2;
+
+use crate::Grid;
+
+/// Settings for [`Grid::to_image`] and the other exporters.
+#[derive(Clone, Debug)]
+pub struct ImageOptions {
+    /// Width and height of a cell in pixels.
+    pub scale: u32,
+    /// Drawn along the top and left edge of every cell, needs a `scale` of at
+    /// least `2`.
+    pub grid_lines: Option<Rgb<u8>>,
+    /// Drawn on top of the cells, in order.
+    pub overlays: Vec<Overlay>,
+}
+
+impl Default for ImageOptions {
+    fn default() -> Self {
+        Self {
+            scale: 1,
+            grid_lines: None,
+            overlays: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Overlay {
+    pub positions: Vec<Vector2<usize>>,
+    pub color: Rgb<u8>,
+    /// Used instead of the cell in text output.
+    pub symbol: char,
+    /// Connect consecutive positions with a line instead of filling the cells.
+    pub path: bool,
+}
+
+impl Overlay {
+    #[must_use]
+    pub fn points(positions: Vec<Vector2<usize>>, color: Rgb<u8>) -> Self {
+        Self {
+            positions,
+            color,
+            symbol: '*',
+            path: false,
+        }
+    }
+
+    #[must_use]
+    pub fn path(positions: Vec<Vector2<usize>>, color: Rgb<u8>) -> Self {
+        Self {
+            positions,
+            color,
+            symbol: '*',
+            path: true,
+        }
+    }
+}
+
+fn dimensions(size: Vector2<usize>, scale: u32) -> ImageResult<(u32, u32)> {
+    let dimension = |v: usize| {
+        u32::try_from(v)
+            .ok()
+            .and_then(|v| v.checked_mul(scale))
+            .ok_or_else(|| {
+                ImageError::Limits(LimitError::from_kind(LimitErrorKind::DimensionError))
+            })
+    };
+
+    Ok((dimension(size.x)?, dimension(size.y)?))
+}
+
+// pixel coordinates of the top left corner of a cell
+pub(crate) fn corner(pos: Vector2<usize>, scale: u32) -> Option<(u32, u32)> {
+    let x = u32::try_from(pos.x).ok()?.checked_mul(scale)?;
+    let y = u32::try_from(pos.y).ok()?.checked_mul(scale)?;
+
+    Some((x, y))
+}
+
+impl<T> Grid<T> {
+    pub fn to_image<F>(&self, options: &ImageOptions, mut color: F) -> ImageResult<RgbImage>
+    where
+        F: FnMut(&T) -> Rgb<u8>,
+    {
+        let scale = options.scale.max(1);
+        let (width, height) = dimensions(self.size, scale)?;
+
+        let mut image = RgbImage::new(width, height);
+        for (pos, v) in self.iter() {
+            if let Some(corner) = corner(pos, scale) {
+                fill(&mut image, corner, scale, color(v));
+            }
+        }
+
+        if let Some(line) = options.grid_lines.filter(|_| scale > 1) {
+            for (x, y, pixel) in image.enumerate_pixels_mut() {
+                if x % scale == 0 || y % scale == 0 {
+                    *pixel = line;
+                }
+            }
+        }
+
+        for overlay in &options.overlays {
+            let corners = overlay
+                .positions
+                .iter()
+                .filter(|pos| self.contains(**pos))
+                .filter_map(|pos| corner(*pos, scale));
+
+            if overlay.path {
+                let thickness = (scale / 3).max(1);
+                let centers = corners
+                    .map(|(x, y)| (x + scale / 2, y + scale / 2))
+                    .collect::<Vec<_>>();
+                for pair in centers.windows(2) {
+                    line(&mut image, pair[0], pair[1], thickness, overlay.color);
+                }
+                if let [single] = centers.as_slice() {
+                    line(&mut image, *single, *single, thickness, overlay.color);
+                }
+            } else {
+                for corner in corners {
+                    fill(&mut image, corner, scale, overlay.color);
+                }
+            }
+        }
+
+        Ok(image)
+    }
+
+    /// Binary PPM (`P6`) of [`Grid::to_image`].
+    pub fn to_ppm<F>(&self, options: &ImageOptions, color: F) -> ImageResult<Vec<u8>>
+    where
+        F: FnMut(&T) -> Rgb<u8>,
+    {
+        let image = self.to_image(options, color)?;
+
+        let mut ret = format!("P6\n{} {}\n255\n", image.width(), image.height()).into_bytes();
+        ret.extend_from_slice(image.as_raw());
+
+        Ok(ret)
+    }
+
+    /// Same layout as [`Grid::to_image`], with one rectangle per run of equally
+    /// colored cells in a row.
+    pub fn to_svg<F>(&self, options: &ImageOptions, mut color: F) -> ImageResult<String>
+    where
+        F: FnMut(&T) -> Rgb<u8>,
+    {
+        let scale = options.scale.max(1);
+        let (width, height) = dimensions(self.size, scale)?;
+
+        let mut ret = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+             viewBox=\"0 0 {width} {height}\" shape-rendering=\"crispEdges\">\n"
+        );
+
+        for (y, row) in self.data.chunks(self.size.x.max(1)).enumerate() {
+            let mut x = 0;
+            let colors = row.iter().map(&mut color).collect::<Vec<_>>();
+            for run in colors.chunk_by(|a, b| a == b) {
+                let _ = writeln!(
+                    ret,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{scale}\" fill=\"{}\"/>",
+                    x * scale as usize,
+                    y * scale as usize,
+                    run.len() * scale as usize,
+                    svg_color(run[0]),
+                );
+                x += run.len();
+            }
+        }
+
+        if let Some(line) = options.grid_lines.filter(|_| scale > 1) {
+            let line = svg_color(line);
+            for x in (0..width).step_by(scale as usize) {
+                let _ = writeln!(
+                    ret,
+                    "<line x1=\"{x}.5\" y1=\"0\" x2=\"{x}.5\" y2=\"{height}\" stroke=\"{line}\"/>"
+                );
+            }
+            for y in (0..height).step_by(scale as usize) {
+                let _ = writeln!(
+                    ret,
+                    "<line x1=\"0\" y1=\"{y}.5\" x2=\"{width}\" y2=\"{y}.5\" stroke=\"{line}\"/>"
+                );
+            }
+        }
+
+        for overlay in &options.overlays {
+            let fill = svg_color(overlay.color);
+            let corners = overlay
+                .positions
+                .iter()
+                .filter(|pos| self.contains(**pos))
+                .filter_map(|pos| corner(*pos, scale));
+
+            if overlay.path {
+                let points = corners
+                    .map(|(x, y)| format!("{},{}", x + scale / 2, y + scale / 2))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                let _ = writeln!(
+                    ret,
+                    "<polyline points=\"{points}\" fill=\"none\" stroke=\"{fill}\" \
+                     stroke-width=\"{}\" stroke-linecap=\"square\"/>",
+                    (scale / 3).max(1)
+                );
+            } else {
+                for (x, y) in corners {
+                    let _ = writeln!(
+                        ret,
+                        "<rect x=\"{x}\" y=\"{y}\" width=\"{scale}\" height=\"{scale}\" \
+                         fill=\"{fill}\"/>"
+                    );
+                }
+            }
+        }
+
+        ret.push_str("</svg>\n");
+
+        Ok(ret)
+    }
+
+    /// [`Grid::render_with`] with the overlay symbols drawn on top, scale and
+    /// grid lines are ignored.
+    pub fn to_text<F>(&self, options: &ImageOptions, mut f: F) -> String
+    where
+        F: FnMut(&T) -> char,
+    {
+        let mut chars = self.iter().map(|(_, v)| f(v)).collect::<Vec<_>>();
+        for overlay in &options.overlays {
+            for pos in &overlay.positions {
+                if let Some(i) = self.index_of(*pos) {
+                    chars[i] = overlay.symbol;
+                }
+            }
+        }
+
+        let mut ret = String::with_capacity(chars.len() + self.size.y);
+        for (y, row) in chars.chunks(self.size.x.max(1)).enumerate() {
+            if y != 0 {
+                ret.push('\n');
+            }
+            ret.extend(row);
+        }
+
+        ret
+    }
+
+    /// The format is picked from the extension, `svg` and `ppm` are written by
+    /// [`Grid::to_svg`] and [`Grid::to_ppm`], anything else by the `image`
+    /// crate.
+    pub fn save_image_with<P, F>(
+        &self,
+        path: P,
+        options: &ImageOptions,
+        color: F,
+    ) -> ImageResult<()>
+    where
+        P: AsRef<Path>,
+        F: FnMut(&T) -> Rgb<u8>,
+    {
+        let path = path.as_ref();
+        let extension = path
+            .extension()
+            .and_then(|v| v.to_str())
+            .map(str::to_ascii_lowercase);
+
+        match extension.as_deref() {
+            Some("svg") => fs::write(path, self.to_svg(options, color)?)?,
+            Some("ppm") => fs::write(path, self.to_ppm(options, color)?)?,
+            _ => self.to_image(options, color)?.save(path)?,
+        }
+
+        Ok(())
+    }
+}
+
+fn svg_color(Rgb([r, g, b]): Rgb<u8>) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+pub(crate) fn fill(image: &mut RgbImage, (x, y): (u32, u32), size: u32, color: Rgb<u8>) {
+    for dy in 0..size {
+        for dx in 0..size {
+            if let Some(pixel) = image.get_pixel_mut_checked(x + dx, y + dy) {
+                *pixel = color;
+            }
+        }
+    }
+}
+
+// Bresenham with a square brush centered on the line
+fn line(image: &mut RgbImage, from: (u32, u32), to: (u32, u32), thickness: u32, color: Rgb<u8>) {
+    let (mut x, mut y) = (i64::from(from.0), i64::from(from.1));
+    let (x1, y1) = (i64::from(to.0), i64::from(to.1));
+
+    let dx = (x1 - x).abs();
+    let dy = -(y1 - y).abs();
+    let sx = if x < x1 { 1 } else { -1 };
+    let sy = if y < y1 { 1 } else { -1 };
+    let mut err = dx + dy;
+
+    let half = i64::from(thickness / 2);
+    loop {
+        let corner_x = u32::try_from((x - half).max(0)).unwrap_or(0);
+        let corner_y = u32::try_from((y - half).max(0)).unwrap_or(0);
+        fill(image, (corner_x, corner_y), thickness, color);
+
+        if x == x1 && y == y1 {
+            break;
+        }
+
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            x += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            y += sy;
+        }
+    }
+}
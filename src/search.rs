@@ -0,0 +1,309 @@
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+};
+
+use nalgebra::Vector2;
+use num::Zero;
+
+use crate::Grid;
+
+/// Costs and predecessors of every node visited by a search.
+#[derive(Clone, Debug)]
+pub struct Search<N, C> {
+    costs: HashMap<N, C>,
+    parents: HashMap<N, N>,
+    target: Option<N>,
+}
+
+impl<N, C> Search<N, C>
+where
+    N: Hash + Eq + Clone,
+    C: Copy,
+{
+    fn new() -> Self {
+        Self {
+            costs: HashMap::new(),
+            parents: HashMap::new(),
+            target: None,
+        }
+    }
+
+    /// The first node for which the `success` predicate held.
+    #[must_use]
+    pub fn target(&self) -> Option<&N> {
+        self.target.as_ref()
+    }
+
+    #[must_use]
+    pub fn target_cost(&self) -> Option<C> {
+        self.target.as_ref().and_then(|v| self.cost(v))
+    }
+
+    #[must_use]
+    pub fn target_path(&self) -> Option<Vec<N>> {
+        self.target.as_ref().and_then(|v| self.path(v))
+    }
+
+    #[must_use]
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.costs.get(node).copied()
+    }
+
+    #[must_use]
+    pub fn costs(&self) -> &HashMap<N, C> {
+        &self.costs
+    }
+
+    /// Path from one of the starts to `node`, both ends included.
+    #[must_use]
+    pub fn path(&self, node: &N) -> Option<Vec<N>> {
+        if !self.costs.contains_key(node) {
+            return None;
+        }
+
+        let mut ret = vec![node.clone()];
+        let mut current = node;
+        while let Some(parent) = self.parents.get(current) {
+            ret.push(parent.clone());
+            current = parent;
+        }
+        ret.reverse();
+
+        Some(ret)
+    }
+}
+
+struct Queued<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for Queued<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for Queued<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Queued<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Queued<N, C> {
+    // reversed, `BinaryHeap` is a max-heap
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+/// Breadth-first search, every edge costs `1`. Stops at the first node
+/// satisfying `success`, or once everything reachable has been visited.
+pub fn bfs<N, S, FN, IN, FS>(starts: S, mut successors: FN, mut success: FS) -> Search<N, usize>
+where
+    N: Hash + Eq + Clone,
+    S: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FS: FnMut(&N) -> bool,
+{
+    let mut ret = Search::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if let Entry::Vacant(entry) = ret.costs.entry(start.clone()) {
+            entry.insert(0);
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((node, cost)) = queue.pop_front() {
+        if success(&node) {
+            ret.target = Some(node);
+            break;
+        }
+
+        for next in successors(&node) {
+            if let Entry::Vacant(entry) = ret.costs.entry(next.clone()) {
+                entry.insert(cost + 1);
+                ret.parents.insert(next.clone(), node.clone());
+                queue.push_back((next, cost + 1));
+            }
+        }
+    }
+
+    ret
+}
+
+/// Dijkstra's algorithm, `successors` yields neighbors with the cost of the
+/// edge leading to them.
+pub fn dijkstra<N, C, S, FN, IN, FS>(starts: S, successors: FN, success: FS) -> Search<N, C>
+where
+    N: Hash + Eq + Clone,
+    C: Zero + Ord + Copy,
+    S: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FS: FnMut(&N) -> bool,
+{
+    astar(starts, successors, |_| C::zero(), success)
+}
+
+/// A* search, `heuristic` must never overestimate the remaining cost or the
+/// returned costs may not be minimal.
+pub fn astar<N, C, S, FN, IN, FH, FS>(
+    starts: S,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut success: FS,
+) -> Search<N, C>
+where
+    N: Hash + Eq + Clone,
+    C: Zero + Ord + Copy,
+    S: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FS: FnMut(&N) -> bool,
+{
+    let mut ret = Search::new();
+    let mut queue = BinaryHeap::new();
+
+    for start in starts {
+        if let Entry::Vacant(entry) = ret.costs.entry(start.clone()) {
+            entry.insert(C::zero());
+            queue.push(Queued {
+                priority: heuristic(&start),
+                cost: C::zero(),
+                node: start,
+            });
+        }
+    }
+
+    while let Some(Queued { cost, node, .. }) = queue.pop() {
+        if ret.costs.get(&node).is_some_and(|v| *v < cost) {
+            continue; // stale entry
+        }
+
+        if success(&node) {
+            ret.target = Some(node);
+            break;
+        }
+
+        for (next, edge) in successors(&node) {
+            let next_cost = cost + edge;
+            match ret.costs.entry(next.clone()) {
+                Entry::Occupied(entry) if *entry.get() <= next_cost => continue,
+                Entry::Occupied(mut entry) => {
+                    entry.insert(next_cost);
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(next_cost);
+                }
+            }
+
+            ret.parents.insert(next.clone(), node.clone());
+            queue.push(Queued {
+                priority: next_cost + heuristic(&next),
+                cost: next_cost,
+                node: next,
+            });
+        }
+    }
+
+    ret
+}
+
+impl<T> Grid<T> {
+    /// [`bfs`] over the 4-connected cells for which `passable` holds.
+    pub fn bfs<P>(&self, start: Vector2<usize>, mut passable: P) -> Search<Vector2<usize>, usize>
+    where
+        P: FnMut(Vector2<usize>, &T) -> bool,
+    {
+        bfs(
+            [start],
+            |pos| {
+                self.neighbors_cardinal(*pos)
+                    .filter(|(pos, v)| passable(*pos, v))
+                    .map(|(pos, _)| pos)
+                    .collect::<Vec<_>>()
+            },
+            |_| false,
+        )
+    }
+
+    /// Shortest 4-connected path from `start` to `goal` through cells for
+    /// which `passable` holds.
+    #[must_use]
+    pub fn shortest_path<P>(
+        &self,
+        start: Vector2<usize>,
+        goal: Vector2<usize>,
+        mut passable: P,
+    ) -> Option<(usize, Vec<Vector2<usize>>)>
+    where
+        P: FnMut(Vector2<usize>, &T) -> bool,
+    {
+        let search = bfs(
+            [start],
+            |pos| {
+                self.neighbors_cardinal(*pos)
+                    .filter(|(pos, v)| passable(*pos, v))
+                    .map(|(pos, _)| pos)
+                    .collect::<Vec<_>>()
+            },
+            |pos| *pos == goal,
+        );
+
+        Some((search.target_cost()?, search.target_path()?))
+    }
+
+    /// [`dijkstra`] over the 4-connected cells, `cost` is the cost of
+    /// entering a cell or `None` if it can't be entered.
+    pub fn dijkstra<C, F>(&self, start: Vector2<usize>, mut cost: F) -> Search<Vector2<usize>, C>
+    where
+        C: Zero + Ord + Copy,
+        F: FnMut(Vector2<usize>, &T) -> Option<C>,
+    {
+        dijkstra(
+            [start],
+            |pos| {
+                self.neighbors_cardinal(*pos)
+                    .filter_map(|(pos, v)| cost(pos, v).map(|cost| (pos, cost)))
+                    .collect::<Vec<_>>()
+            },
+            |_| false,
+        )
+    }
+
+    /// Cheapest 4-connected path from `start` to `goal`, see
+    /// [`Grid::dijkstra`].
+    #[must_use]
+    pub fn cheapest_path<C, F>(
+        &self,
+        start: Vector2<usize>,
+        goal: Vector2<usize>,
+        mut cost: F,
+    ) -> Option<(C, Vec<Vector2<usize>>)>
+    where
+        C: Zero + Ord + Copy,
+        F: FnMut(Vector2<usize>, &T) -> Option<C>,
+    {
+        let search = dijkstra(
+            [start],
+            |pos| {
+                self.neighbors_cardinal(*pos)
+                    .filter_map(|(pos, v)| cost(pos, v).map(|cost| (pos, cost)))
+                    .collect::<Vec<_>>()
+            },
+            |pos| *pos == goal,
+        );
+
+        Some((search.target_cost()?, search.target_path()?))
+    }
+}
@@ -0,0 +1,245 @@
+use std::{
+    collections::{hash_map, HashMap},
+    path::Path,
+};
+
+use getset::CopyGetters;
+use image::{
+    error::{LimitError, LimitErrorKind},
+    ImageError, ImageResult, Rgb,
+};
+use nalgebra::Vector2;
+
+use crate::{Direction, Grid, GridError};
+
+/// Unbounded counterpart of [`Grid`], positions may be negative.
+#[derive(Clone, Debug)]
+pub struct SparseGrid<T> {
+    data: HashMap<Vector2<i64>, T>,
+    bounds: Option<Bounds>,
+}
+
+/// Inclusive bounding box of the occupied cells of a [`SparseGrid`].
+#[derive(CopyGetters, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Bounds {
+    #[getset(get_copy = "pub")]
+    min: Vector2<i64>,
+    #[getset(get_copy = "pub")]
+    max: Vector2<i64>,
+}
+
+impl Bounds {
+    fn new(pos: Vector2<i64>) -> Self {
+        Self { min: pos, max: pos }
+    }
+
+    fn extend(&mut self, pos: Vector2<i64>) {
+        self.min = self.min.inf(&pos);
+        self.max = self.max.sup(&pos);
+    }
+
+    fn is_on_edge(&self, pos: Vector2<i64>) -> bool {
+        pos.x == self.min.x || pos.x == self.max.x || pos.y == self.min.y || pos.y == self.max.y
+    }
+
+    #[must_use]
+    pub fn contains(&self, pos: Vector2<i64>) -> bool {
+        (self.min.x..=self.max.x).contains(&pos.x) && (self.min.y..=self.max.y).contains(&pos.y)
+    }
+
+    /// `None` if the size doesn't fit in `usize`.
+    #[must_use]
+    pub fn size(&self) -> Option<Vector2<usize>> {
+        let x = self.max.x.abs_diff(self.min.x).checked_add(1)?;
+        let y = self.max.y.abs_diff(self.min.y).checked_add(1)?;
+
+        Some(Vector2::new(
+            usize::try_from(x).ok()?,
+            usize::try_from(y).ok()?,
+        ))
+    }
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            data: HashMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// `None` while the grid is empty.
+    #[must_use]
+    pub fn bounds(&self) -> Option<Bounds> {
+        self.bounds
+    }
+
+    #[must_use]
+    pub fn get(&self, pos: Vector2<i64>) -> Option<&T> {
+        self.data.get(&pos)
+    }
+
+    #[must_use]
+    pub fn get_mut(&mut self, pos: Vector2<i64>) -> Option<&mut T> {
+        self.data.get_mut(&pos)
+    }
+
+    pub fn insert(&mut self, pos: Vector2<i64>, v: T) -> Option<T> {
+        self.extend_bounds(pos);
+        self.data.insert(pos, v)
+    }
+
+    pub fn get_or_insert_with<F>(&mut self, pos: Vector2<i64>, f: F) -> &mut T
+    where
+        F: FnOnce() -> T,
+    {
+        self.extend_bounds(pos);
+        self.data.entry(pos).or_insert_with(f)
+    }
+
+    pub fn remove(&mut self, pos: Vector2<i64>) -> Option<T> {
+        let ret = self.data.remove(&pos)?;
+
+        if self.bounds.is_some_and(|bounds| bounds.is_on_edge(pos)) {
+            self.bounds = self.data.keys().fold(None, |acc: Option<Bounds>, pos| {
+                let mut acc = acc.unwrap_or_else(|| Bounds::new(*pos));
+                acc.extend(*pos);
+                Some(acc)
+            });
+        }
+
+        Some(ret)
+    }
+
+    pub fn clear(&mut self) {
+        self.data.clear();
+        self.bounds = None;
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Vector2<i64>, &T)> {
+        self.data.iter().map(|(pos, v)| (*pos, v))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Vector2<i64>, &mut T)> {
+        self.data.iter_mut().map(|(pos, v)| (*pos, v))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Vector2<i64>> + '_ {
+        self.data.keys().copied()
+    }
+
+    #[must_use]
+    pub fn get_dir(&self, pos: Vector2<i64>, dir: Direction) -> Option<&T> {
+        self.get(dir.apply(pos))
+    }
+
+    #[must_use]
+    pub fn get_mut_dir(&mut self, pos: Vector2<i64>, dir: Direction) -> Option<&mut T> {
+        self.get_mut(dir.apply(pos))
+    }
+
+    pub fn neighbors(&self, pos: Vector2<i64>) -> impl Iterator<Item = (Vector2<i64>, &T)> {
+        self.neighbors_in(pos, &Direction::ALL)
+    }
+
+    pub fn neighbors_cardinal(
+        &self,
+        pos: Vector2<i64>,
+    ) -> impl Iterator<Item = (Vector2<i64>, &T)> {
+        self.neighbors_in(pos, &Direction::CARDINAL)
+    }
+
+    fn neighbors_in<'a>(
+        &'a self,
+        pos: Vector2<i64>,
+        dirs: &'static [Direction],
+    ) -> impl Iterator<Item = (Vector2<i64>, &'a T)> {
+        dirs.iter().filter_map(move |dir| {
+            let pos = dir.apply(pos);
+            self.get(pos).map(|v| (pos, v))
+        })
+    }
+
+    /// Dense copy of the bounding box, `(0, 0)` of the returned grid is
+    /// [`Bounds::min`]. Unoccupied cells are filled with `T::default()`.
+    /// [`GridError::Empty`] if there are no cells and
+    /// [`GridError::TooManyElements`] if the bounding box is too large.
+    pub fn to_grid(&self) -> Result<Grid<T>, GridError>
+    where
+        T: Clone + Default,
+    {
+        let bounds = self.bounds.ok_or(GridError::Empty)?;
+        let mut grid = Grid::new_default(bounds.size().ok_or(GridError::TooManyElements)?)?;
+
+        for (pos, v) in self.iter() {
+            let offset = |v: i64, min: i64| {
+                usize::try_from(v.abs_diff(min)).map_err(|_| GridError::TooManyElements)
+            };
+            let pos = Vector2::new(offset(pos.x, bounds.min.x)?, offset(pos.y, bounds.min.y)?);
+            grid[pos] = v.clone();
+        }
+
+        Ok(grid)
+    }
+
+    /// Saves the bounding box through [`Grid::save_image`], does nothing if
+    /// the grid is empty.
+    pub fn save_image<P>(&self, path: P) -> ImageResult<()>
+    where
+        P: AsRef<Path>,
+        T: Clone + Default,
+        for<'a> &'a T: Into<Rgb<u8>>,
+    {
+        match self.to_grid() {
+            Ok(grid) => grid.save_image(path),
+            Err(GridError::Empty) => Ok(()),
+            Err(_) => Err(ImageError::Limits(LimitError::from_kind(
+                LimitErrorKind::DimensionError,
+            ))),
+        }
+    }
+
+    fn extend_bounds(&mut self, pos: Vector2<i64>) {
+        match &mut self.bounds {
+            Some(bounds) => bounds.extend(pos),
+            None => self.bounds = Some(Bounds::new(pos)),
+        }
+    }
+}
+
+impl<T> FromIterator<(Vector2<i64>, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Vector2<i64>, T)>>(iter: I) -> Self {
+        let mut ret = Self::new();
+        for (pos, v) in iter {
+            ret.insert(pos, v);
+        }
+
+        ret
+    }
+}
+
+impl<T> IntoIterator for SparseGrid<T> {
+    type Item = (Vector2<i64>, T);
+    type IntoIter = hash_map::IntoIter<Vector2<i64>, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
@@ -0,0 +1,162 @@
+use std::{
+    fmt::{self, Display, Write},
+    str::FromStr,
+};
+
+use tap::prelude::*;
+use thiserror::Error;
+
+use crate::{Grid, GridError};
+
+/// A grid cell that's rendered as, and parsed from, a single character.
+pub trait Tile: Sized {
+    fn to_char(&self) -> char;
+    fn from_char(c: char) -> Option<Self>;
+}
+
+impl Tile for char {
+    fn to_char(&self) -> char {
+        *self
+    }
+
+    fn from_char(c: char) -> Option<Self> {
+        Some(c)
+    }
+}
+
+impl Tile for u8 {
+    fn to_char(&self) -> char {
+        char::from(*self)
+    }
+
+    fn from_char(c: char) -> Option<Self> {
+        u8::try_from(c).ok().filter(u8::is_ascii)
+    }
+}
+
+impl Tile for bool {
+    fn to_char(&self) -> char {
+        if *self {
+            '#'
+        } else {
+            '.'
+        }
+    }
+
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// One line per row, without a trailing newline.
+    pub fn render_with<F>(&self, mut f: F) -> String
+    where
+        F: FnMut(&T) -> char,
+    {
+        let mut ret = String::with_capacity(self.data.len() + self.size.y);
+        for (y, row) in self.data.chunks(self.size.x.max(1)).enumerate() {
+            if y != 0 {
+                ret.push('\n');
+            }
+            ret.extend(row.iter().map(&mut f));
+        }
+
+        ret
+    }
+
+    /// Like [`Grid::render_with`], with column numbers above and row numbers
+    /// to the left.
+    pub fn render_with_rulers<F>(&self, mut f: F) -> String
+    where
+        F: FnMut(&T) -> char,
+    {
+        let row_width = self.size.y.saturating_sub(1).to_string().len();
+        let column_digits = self.size.x.saturating_sub(1).to_string().len();
+
+        let places = std::iter::successors(Some(1_usize), |v| v.checked_mul(10))
+            .take(column_digits)
+            .collect::<Vec<_>>();
+
+        let mut ret = String::new();
+        for place in places.into_iter().rev() {
+            ret.extend(std::iter::repeat_n(' ', row_width + 1));
+            for x in 0..self.size.x {
+                match u32::try_from(x / place % 10) {
+                    Ok(digit) if x % place == 0 => {
+                        ret.push(char::from_digit(digit, 10).unwrap_or(' '));
+                    }
+                    _ => ret.push(' '),
+                }
+            }
+            ret.truncate(ret.trim_end_matches(' ').len());
+            ret.push('\n');
+        }
+
+        for (y, row) in self.data.chunks(self.size.x.max(1)).enumerate() {
+            let _ = write!(ret, "{y:>row_width$} ");
+            ret.extend(row.iter().map(&mut f));
+            ret.push('\n');
+        }
+        ret.pop();
+
+        ret
+    }
+
+    pub fn parse_with<F>(input: &str, mut f: F) -> Result<Self, GridParseError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        input
+            .lines()
+            .enumerate()
+            .map(|(y, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(x, c)| {
+                        f(c).ok_or(GridParseError::InvalidChar {
+                            line: y + 1,
+                            column: x + 1,
+                            c,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .pipe(Grid::try_from)
+            .map_err(GridParseError::Grid)
+    }
+}
+
+/// Renders through [`Tile`], the alternate form (`{:#}`) adds coordinate
+/// rulers.
+impl<T: Tile> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.write_str(&self.render_with_rulers(Tile::to_char))
+        } else {
+            f.write_str(&self.render_with(Tile::to_char))
+        }
+    }
+}
+
+impl<T: Tile> FromStr for Grid<T> {
+    type Err = GridParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, T::from_char)
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum GridParseError {
+    /// Lines and columns start at `1` like in [`ParseError`](crate::ParseError).
+    #[error("invalid character `{c}` at {line}:{column}")]
+    InvalidChar { line: usize, column: usize, c: char },
+    #[error(transparent)]
+    Grid(GridError),
+}
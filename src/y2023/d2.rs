@@ -1,8 +1,8 @@
 use std::str::FromStr;
 
-use anyhow::{bail, Context, Error, Result};
+use anyhow::{bail, Error, Result};
 
-use crate::Solution;
+use crate::{scan, Solution};
 
 #[derive(Default)]
 pub struct Part1(u64);
@@ -11,7 +11,7 @@ impl Solution for Part1 {
     type Type = u64;
 
     fn process_line(&mut self, line: &str) -> Result<()> {
-        let game = Game::from_str(line).unwrap();
+        let game = Game::from_str(line)?;
 
         if game.set.red <= 12 && game.set.green <= 13 && game.set.blue <= 14 {
             self.0 += game.id;
@@ -32,7 +32,7 @@ impl Solution for Part2 {
     type Type = u64;
 
     fn process_line(&mut self, line: &str) -> Result<()> {
-        let game = Game::from_str(line).unwrap();
+        let game = Game::from_str(line)?;
 
         let power = game.set.red * game.set.green * game.set.blue;
         self.0 += power;
@@ -54,43 +54,23 @@ pub struct Game {
 impl FromStr for Game {
     type Err = Error;
 
-    fn from_str(mut s: &str) -> std::result::Result<Self, Self::Err> {
-        s = &s[5..];
-        let (id, rest) = s.split_once(':').context("expected `:`")?;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (id, rest): (u64, &str) = scan!(s, "Game {u64}: {rest}")?;
 
         let set = rest
             .split([';', ','])
             .try_fold(Set::default(), |mut set, v| {
-                let (v, color) = v
-                    .trim_start()
-                    .split_once(' ')
-                    .context("cannot split color")?;
+                let (v, color): (u64, &str) = scan!(v.trim_start(), "{u64} {word}")?;
                 match color {
-                    "red" => {
-                        set.red = set.red.max(v.parse().with_context(|| {
-                            format!("unable to parse value for red, found: `{v}`")
-                        })?);
-                    }
-                    "green" => {
-                        set.green = set.green.max(v.parse().with_context(|| {
-                            format!("unable to parse value for green, found: `{v}`")
-                        })?);
-                    }
-                    "blue" => {
-                        set.blue = set.blue.max(v.parse().with_context(|| {
-                            format!("unable to parse value for blue, found: `{v}`")
-                        })?);
-                    }
+                    "red" => set.red = set.red.max(v),
+                    "green" => set.green = set.green.max(v),
+                    "blue" => set.blue = set.blue.max(v),
                     _ => bail!("invalid color: `{color}`"),
                 }
 
                 Ok::<_, Error>(set)
             })?;
 
-        let id = id
-            .parse()
-            .with_context(|| format!("unable to parse id, found: `{id}`"))?;
-
         Ok(Self { id, set })
     }
 }
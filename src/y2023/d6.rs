@@ -1,32 +1,29 @@
 use std::{fmt::Write, str::FromStr};
 
-use anyhow::{anyhow, bail, Result};
+use anyhow::Result;
 use tap::prelude::*;
+use winnow::{ascii::line_ending, combinator::separated_pair};
 
-pub struct Parser;
+use crate::{
+    math,
+    parse::{self, labelled, uints, ParseError},
+};
 
-impl Parser {
-    pub fn parse(input: &str) -> Result<Parsed> {
-        let mut lines = input.lines();
+pub struct Parser;
 
-        let times = lines
-            .next()
-            .unwrap()
-            .trim_start_matches("Time:")
-            .split_ascii_whitespace()
-            .map(|v| u64::from_str(v).map_err(|_| anyhow!("unable to parse: `{v}`")))
-            .collect::<Result<Vec<u64>>>()?;
-        let distances = lines
-            .next()
-            .unwrap()
-            .trim_start_matches("Distance:")
-            .split_ascii_whitespace()
-            .map(|v| u64::from_str(v).map_err(|_| anyhow!("unable to parse: `{v}`")))
-            .collect::<Result<Vec<u64>>>()?;
+impl crate::Parser for Parser {
+    type Type<'a> = Parsed;
+    type Error = ParseError;
 
-        if lines.next().is_some() {
-            bail!("trailing input")
-        }
+    fn parse(input: &str) -> Result<Parsed, ParseError> {
+        let (times, distances) = parse::parse(
+            input,
+            separated_pair(
+                labelled("Time", uints),
+                line_ending,
+                labelled("Distance", uints),
+            ),
+        )?;
 
         Ok(Parsed { times, distances })
     }
@@ -38,17 +35,14 @@ pub struct Parsed {
     pub distances: Vec<u64>,
 }
 
-pub fn part1(parsed: &Parsed) -> u64 {
+pub fn part1(parsed: &Parsed) -> Option<u64> {
     parsed
         .times
         .iter()
         .zip(parsed.distances.iter())
-        .map(|(time, distance)| {
-            (1..*time)
-                .filter_map(|charge| is_beating(*time, *distance, charge))
-                .count() as u64
+        .try_fold(1_u64, |acc, (time, distance)| {
+            acc.checked_mul(math::count_products_above(*time, *distance))
         })
-        .product()
 }
 
 pub fn part2(parsed: &Parsed) -> u64 {
@@ -71,19 +65,5 @@ pub fn part2(parsed: &Parsed) -> u64 {
         .pipe_as_ref(u64::from_str)
         .unwrap();
 
-    (1..time)
-        .filter_map(|charge| is_beating(time, distance, charge))
-        .count() as u64
-}
-
-#[must_use]
-pub fn is_beating(time: u64, distance: u64, charge: u64) -> Option<u64> {
-    let left = time - charge;
-    let new_distance = left * charge;
-
-    if new_distance > distance {
-        Some(new_distance)
-    } else {
-        None
-    }
+    math::count_products_above(time, distance)
 }